@@ -3,12 +3,14 @@
 //! Use the [`answers::Builder`][struct@Builder] to construct an [`answers::Request`][Request] struct
 use std::collections::HashMap;
 
-use crate::OPENAI_URL;
 use crate::{into_vec::IntoVec, model::Model};
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
-use super::RequestInfo;
+use super::{
+    check_exclusive, check_logit_bias, check_range, user_hash, Expandable, ObjectType, RequestInfo,
+    Usage,
+};
 /// Answers questions from provided context
 ///
 /// # OpenAi documentation
@@ -18,7 +20,8 @@ use super::RequestInfo;
 /// The relevant context is combined with the provided examples and question to create the prompt for completion.
 ///
 /// # Example
-/// ```ignore
+/// ```
+/// # use gpt3_rs::{api::answers, Model};
 /// let request = answers::Builder::default()
 ///     .model(Model::Curie)
 ///     .search_model(Model::Ada)
@@ -40,14 +43,14 @@ use super::RequestInfo;
 /// ```ignore
 /// model, question, examples, examples_context
 /// ```
-#[derive(Debug, Clone, PartialEq, Serialize, Builder)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Builder)]
 #[builder_struct_attr(doc = "# Required")]
 #[builder_struct_attr(doc = "[`model`][Self::model()]")]
 #[builder_struct_attr(doc = "[`question`][Self::question()]")]
 #[builder_struct_attr(doc = "[`examples`][Self::examples()]")]
 #[builder_struct_attr(doc = "[`examples_context`][Self::examples_context()]")]
 #[builder_struct_attr(doc = "")]
-#[builder(name = "Builder")]
+#[builder(name = "Builder", build_fn(validate = "Self::validate"))]
 pub struct Request {
     /// ID of the engine to use for completion. You can select one of ada, babbage, curie, or davinci.
     //#[builder(!default, setter(!strip_option))]
@@ -115,15 +118,15 @@ pub struct Request {
     /// As an example, you can pass {"50256": -100} to prevent the <|endoftext|> token from being generated.
     #[builder(default, setter(strip_option))]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub logit_bits: Option<HashMap<String, i8>>,
+    pub logit_bias: Option<HashMap<String, i16>>,
     /// A special boolean flag for showing metadata.
     /// If set to true, each document entry in the returned JSON will contain a "metadata" field.
     /// This flag only takes effect when file is set.
     #[builder(default, setter(strip_option))]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub return_metadata: Option<bool>,
-    /// If set to true, the returned JSON will include a "prompt" field containing the final prompt that was used to request a completion. T
-    /// his is mainly useful for debugging purposes.
+    /// If set to true, the returned JSON will include a "prompt" field containing the final prompt that was used to request a completion.
+    /// This is mainly useful for debugging purposes.
     #[builder(default, setter(strip_option))]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub return_prompt: Option<bool>,
@@ -133,6 +136,7 @@ pub struct Request {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub expand: Option<IntoVec<String>>,
     /// A unique identifier representing your end-user, which will help OpenAI to monitor and detect abuse.
+    /// It should not contain identifiable information, see [`Builder::user_hash`].
     #[builder(default, setter(strip_option, into))]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub user: Option<String>,
@@ -142,16 +146,22 @@ pub struct Request {
 pub struct Response {
     /// the answers generated by the request
     pub answers: Vec<String>,
-    /// completion id
-    pub completion: String,
+    /// The completion used to create the response,
+    /// only its id unless `completion` is added to [`expand`](Request::expand)
+    pub completion: Expandable<super::completions::Response>,
     /// the model used for the request
     pub model: String,
     /// the object / action of the request
-    pub object: String,
+    pub object: ObjectType,
     /// the model used to search through the text
     pub search_model: String,
     /// documents sent in the request
     pub selected_documents: Vec<SelectedDocument>,
+    /// The tokens consumed by this request
+    pub usage: Option<Usage>,
+    /// Fields returned by the api that aren't modeled by this crate yet
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -162,9 +172,29 @@ pub struct SelectedDocument {
     pub text: String,
 }
 
+impl Response {
+    /// The first answer, `None` if there are no answers
+    pub fn answer(&self) -> Option<&str> {
+        self.answers.first().map(String::as_str)
+    }
+}
+impl Builder {
+    user_hash!();
+    fn validate(&self) -> Result<(), String> {
+        check_range("temperature", self.temperature, 0.0, 2.0)?;
+        check_logit_bias(&self.logit_bias)?;
+        check_range("logprobs", self.logprobs, 0, 5)?;
+        check_exclusive(
+            ("documents", matches!(self.documents, Some(Some(_)))),
+            ("file", matches!(self.file, Some(Some(_)))),
+            false,
+        )?;
+        Ok(())
+    }
+}
 impl RequestInfo for Request {
-    fn url(&self) -> String {
-        format!("{OPENAI_URL}/answers")
+    fn url(&self, base_url: &str) -> String {
+        format!("{base_url}/answers")
     }
 }
 
@@ -172,3 +202,61 @@ impl RequestInfo for Request {
 impl crate::client::Request for Request {
     type Response = Response;
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::Builder;
+    use crate::Model;
+
+    #[test]
+    fn serialize() {
+        let request = Builder::default()
+            .model(Model::Curie)
+            .question("which puppy is happy?")
+            .documents(["Puppy A is happy", "Puppy B is sad."])
+            .examples_context("In 2017, U.S. life expectancy was 78.6 years.")
+            .examples(&[&[
+                "What is human life expectancy in the United States?",
+                "78 years.",
+            ]])
+            .logit_bias(HashMap::from([("50256".to_string(), -100)]))
+            .build()
+            .unwrap();
+
+        let json = serde_json::to_value(&request).unwrap();
+
+        assert_eq!(json["model"], "curie");
+        assert_eq!(json["examples"][0][1], "78 years.");
+        assert_eq!(json["logit_bias"]["50256"], -100);
+        assert!(json.get("file").is_none());
+    }
+    #[test]
+    fn documents_or_file() {
+        let builder = || {
+            let mut builder = Builder::default();
+            builder
+                .model(Model::Curie)
+                .question("which puppy is happy?")
+                .examples_context("In 2017, U.S. life expectancy was 78.6 years.")
+                .examples(&[&[
+                    "What is human life expectancy in the United States?",
+                    "78 years.",
+                ]]);
+            builder
+        };
+
+        assert_eq!(
+            builder()
+                .documents(["Puppy A is happy", "Puppy B is sad."])
+                .file("file-123")
+                .build()
+                .unwrap_err()
+                .to_string(),
+            "only one of `documents` and `file` can be set"
+        );
+        // without either the question is answered from the examples
+        assert!(builder().build().is_ok());
+    }
+}
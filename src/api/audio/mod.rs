@@ -0,0 +1,183 @@
+//! This module contains other modules that are used to interact with the audio api
+//!
+//! [`speech`] responds with the generated audio,
+//! [`transcriptions`] and [`translations`] upload an [`Audio`] file and respond with a [`Response`] containing the text.
+use std::{collections::HashMap, fmt, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{model::Model, Form, Part};
+
+pub mod speech;
+pub mod transcriptions;
+pub mod translations;
+
+/// The format of a transcript
+///
+/// Only [`Json`](TranscriptFormat::Json) and [`VerboseJson`](TranscriptFormat::VerboseJson)
+/// can be deserialized into a [`Response`], use [`Request::request_raw`](crate::Request::request_raw) for the other formats.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscriptFormat {
+    /// A json object with the text
+    #[default]
+    Json,
+    /// The text
+    Text,
+    /// SubRip subtitles
+    Srt,
+    /// A json object with the text, language, duration and segments
+    VerboseJson,
+    /// WebVTT subtitles
+    Vtt,
+}
+impl fmt::Display for TranscriptFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            TranscriptFormat::Json => "json",
+            TranscriptFormat::Text => "text",
+            TranscriptFormat::Srt => "srt",
+            TranscriptFormat::VerboseJson => "verbose_json",
+            TranscriptFormat::Vtt => "vtt",
+        })
+    }
+}
+
+/// An audio file to upload, in one of these formats: mp3, mp4, mpeg, mpga, m4a, wav, or webm
+///
+/// The bytes are sent as is, the api detects the format from the file name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Audio {
+    /// The file name of the audio, including its extension
+    pub name: String,
+    /// The content of the audio file
+    pub data: Vec<u8>,
+}
+impl Audio {
+    pub fn new(name: String, data: Vec<u8>) -> Self {
+        Audio { name, data }
+    }
+    /// Reads an audio file from disk, using its file name as the name of the upload
+    /// # Example
+    /// ```ignore
+    /// let audio = audio::Audio::from_path("speech.mp3")?;
+    /// ```
+    pub fn from_path(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let path = path.as_ref();
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let data = std::fs::read(path)?;
+        Ok(Audio { name, data })
+    }
+    fn part(&self) -> Part {
+        Part::bytes(self.data.clone()).file_name(self.name.clone())
+    }
+}
+
+/// Creates the multipart form shared by transcriptions and translations
+fn form(
+    file: &Audio,
+    model: &Model,
+    prompt: &Option<String>,
+    response_format: Option<TranscriptFormat>,
+    temperature: Option<f64>,
+) -> Form {
+    let mut form = Form::new()
+        .part("file", file.part())
+        .text("model", model.id().to_string());
+    if let Some(prompt) = prompt {
+        form = form.text("prompt", prompt.clone());
+    }
+    if let Some(response_format) = response_format {
+        form = form.text("response_format", response_format.to_string());
+    }
+    if let Some(temperature) = temperature {
+        form = form.text("temperature", temperature.to_string());
+    }
+    form
+}
+
+/// The transcript created by a request
+///
+/// Only [`text`](Self::text) is returned for [`TranscriptFormat::Json`],
+/// the other fields are set for [`TranscriptFormat::VerboseJson`].
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Response {
+    /// The transcribed text
+    pub text: String,
+    /// The language of the audio
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// The duration of the audio in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration: Option<f64>,
+    /// The segments of the transcript with their timestamps
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub segments: Option<Vec<Segment>>,
+    /// Fields returned by the api that aren't modeled by this crate yet
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+/// A segment of a verbose transcript
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Segment {
+    /// The index of the segment
+    pub id: u64,
+    /// The seek offset of the segment
+    pub seek: u64,
+    /// The start time of the segment in seconds
+    pub start: f64,
+    /// The end time of the segment in seconds
+    pub end: f64,
+    /// The text of the segment
+    pub text: String,
+    /// The tokens of the text
+    pub tokens: Vec<u32>,
+    /// The temperature used to generate the segment
+    pub temperature: f64,
+    /// The average log probability of the tokens, segments below -1 are likely wrong
+    pub avg_logprob: f64,
+    /// The compression ratio of the segment, segments above 2.4 are likely repetitive
+    pub compression_ratio: f64,
+    /// The probability that the segment contains no speech
+    pub no_speech_prob: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Response;
+
+    #[test]
+    fn verbose_response() {
+        let json = r#"{
+            "task": "transcribe",
+            "language": "english",
+            "duration": 2.95,
+            "text": "Hello world.",
+            "segments": [{
+                "id": 0,
+                "seek": 0,
+                "start": 0.0,
+                "end": 2.0,
+                "text": " Hello world.",
+                "tokens": [50364, 2425, 1002, 13, 50464],
+                "temperature": 0.0,
+                "avg_logprob": -0.45,
+                "compression_ratio": 0.72,
+                "no_speech_prob": 0.01
+            }]
+        }"#;
+
+        let response: Response = serde_json::from_str(json).unwrap();
+
+        assert_eq!(response.text, "Hello world.");
+        assert_eq!(response.duration, Some(2.95));
+        assert_eq!(response.segments.unwrap()[0].end, 2.0);
+        assert_eq!(response.extra["task"], "transcribe");
+
+        let response: Response = serde_json::from_str(r#"{"text": "Hello world."}"#).unwrap();
+        assert_eq!(response.segments, None);
+    }
+}
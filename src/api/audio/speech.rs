@@ -0,0 +1,164 @@
+//! Generate audio from text
+//! # Builder
+//! Use the [`audio::speech::Builder`][struct@Builder] to construct an [`audio::speech::Request`][Request] struct
+use std::path::Path;
+
+use bytes::Bytes;
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::{check_range, RequestInfo},
+    model::Model,
+    Client, ClientError,
+};
+/// Generate audio from text
+///
+/// # OpenAi documentation
+/// Generates audio from the input text.
+/// # Example
+/// ```ignore
+/// let request = audio::speech::Builder::default()
+///     .model(Model::from_id("tts-1"))
+///     .input("The quick brown fox jumped over the lazy dog.")
+///     .voice(Voice::Alloy)
+///     .build()
+///     .unwrap();
+///
+/// request.request(&client).await?.save("speech.mp3")?;
+/// ```
+/// # Required
+/// ```ignore
+/// model, input, voice
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Builder)]
+#[builder_struct_attr(doc = "# Required")]
+#[builder_struct_attr(doc = "[`model`][Self::model()]")]
+#[builder_struct_attr(doc = "[`input`][Self::input()]")]
+#[builder_struct_attr(doc = "[`voice`][Self::voice()]")]
+#[builder_struct_attr(doc = "")]
+#[builder(name = "Builder", build_fn(validate = "Self::validate"))]
+pub struct Request {
+    /// One of the available TTS models: `tts-1` or `tts-1-hd`
+    pub model: Model,
+    /// The text to generate audio for. The maximum length is 4096 characters.
+    #[builder(setter(into))]
+    pub input: String,
+    /// The voice to use when generating the audio.
+    pub voice: Voice,
+    /// The format to return the audio in.
+    /// # Default
+    /// Defaults to [`SpeechFormat::Mp3`]
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<SpeechFormat>,
+    /// The speed of the generated audio. Select a value from 0.25 to 4.0.
+    /// # Default
+    /// Defaults to 1.0
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speed: Option<f64>,
+}
+/// The voice of the generated audio
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Voice {
+    Alloy,
+    Echo,
+    Fable,
+    Onyx,
+    Nova,
+    Shimmer,
+}
+/// The format of the generated audio
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SpeechFormat {
+    #[default]
+    Mp3,
+    /// For internet streaming and communication, low latency
+    Opus,
+    /// For digital audio compression, preferred by YouTube, Android, iOS
+    Aac,
+    /// For lossless audio compression, favored by audio enthusiasts for archiving
+    Flac,
+}
+/// A response corresponding to a [`Request`]
+///
+/// The api responds with the audio itself instead of json.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Response {
+    /// The generated audio, in the requested [`SpeechFormat`]
+    pub audio: Bytes,
+}
+impl Response {
+    /// Writes the audio to a file, the extension should match the requested [`SpeechFormat`]
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        std::fs::write(path, &self.audio)
+    }
+}
+
+impl Builder {
+    fn validate(&self) -> Result<(), String> {
+        check_range("speed", self.speed, 0.25, 4.0)?;
+        Ok(())
+    }
+}
+impl RequestInfo for Request {
+    fn url(&self, base_url: &str) -> String {
+        format!("{base_url}/audio/speech")
+    }
+}
+#[cfg_attr(not(feature = "blocking"), async_trait::async_trait)]
+impl crate::client::Request for Request {
+    type Response = Response;
+
+    #[cfg(not(feature = "blocking"))]
+    async fn request(&self, client: &Client) -> Result<Response, ClientError> {
+        let audio = client.request_bytes(self).await?;
+        Ok(Response { audio })
+    }
+    #[cfg(feature = "blocking")]
+    fn request(&self, client: &Client) -> Result<Response, ClientError> {
+        let audio = client.request_bytes(self)?;
+        Ok(Response { audio })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::{Builder, SpeechFormat, Voice};
+    use crate::{transport::MockTransport, Client, Model};
+
+    #[test]
+    fn audio_response() {
+        let transport = Arc::new(MockTransport::new().respond(200, "ID3\u{4}\u{0}"));
+        let client = Client::with_transport("token", transport.clone());
+        let request = Builder::default()
+            .model(Model::from_id("tts-1"))
+            .input("The quick brown fox jumped over the lazy dog.")
+            .voice(Voice::Alloy)
+            .response_format(SpeechFormat::Mp3)
+            .build()
+            .unwrap();
+
+        let response = wait!(client.request(&request)).unwrap();
+
+        assert_eq!(response.audio.as_ref(), b"ID3\x04\x00");
+        let body = transport.requests()[0].json().unwrap();
+        assert_eq!(body["voice"], "alloy");
+        assert_eq!(body["response_format"], "mp3");
+        assert_eq!(
+            transport.requests()[0].url.as_str(),
+            "https://api.openai.com/v1/audio/speech"
+        );
+
+        let path = std::env::temp_dir().join("gpt3_rs_speech_test.mp3");
+        response.save(&path).unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"ID3\x04\x00");
+        std::fs::remove_file(path).unwrap();
+    }
+}
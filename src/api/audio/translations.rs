@@ -0,0 +1,120 @@
+//! Translate audio into English
+//! # Builder
+//! Use the [`audio::translations::Builder`][struct@Builder] to construct an [`audio::translations::Request`][Request] struct
+use derive_builder::Builder;
+
+use crate::api::{check_range, Auth, BuildRequest};
+use crate::model::Model;
+
+use super::{form, Audio, TranscriptFormat};
+/// Translate audio into English
+///
+/// # OpenAi documentation
+/// Translates audio into English.
+/// # Example
+/// ```ignore
+/// let request = audio::translations::Builder::default()
+///     .file(Audio::from_path("german.m4a")?)
+///     .model(Model::from_id("whisper-1"))
+///     .build()
+///     .unwrap();
+/// ```
+/// # Required
+/// ```ignore
+/// file, model
+/// ```
+#[derive(Debug, Clone, PartialEq, Builder)]
+#[builder_struct_attr(doc = "# Required")]
+#[builder_struct_attr(doc = "[`file`][Self::file()]")]
+#[builder_struct_attr(doc = "[`model`][Self::model()]")]
+#[builder_struct_attr(doc = "")]
+#[builder(name = "Builder", build_fn(validate = "Self::validate"))]
+pub struct Request {
+    /// The audio file to translate, in one of these formats: mp3, mp4, mpeg, mpga, m4a, wav, or webm.
+    pub file: Audio,
+    /// ID of the model to use. Only `whisper-1` is currently available.
+    pub model: Model,
+    /// An optional text to guide the model's style or continue a previous audio segment.
+    /// The prompt should be in English.
+    #[builder(default, setter(strip_option, into))]
+    pub prompt: Option<String>,
+    /// The format of the transcript output.
+    /// # Default
+    /// Defaults to [`TranscriptFormat::Json`]
+    #[builder(default, setter(strip_option))]
+    pub response_format: Option<TranscriptFormat>,
+    /// The sampling temperature, between 0 and 1.
+    /// Higher values like 0.8 will make the output more random, while lower values like 0.2 will make it more focused and deterministic.
+    /// If set to 0, the model will use log probability to automatically increase the temperature until certain thresholds are hit.
+    /// # Default
+    /// Defaults to 0
+    #[builder(default, setter(strip_option))]
+    pub temperature: Option<f64>,
+}
+/// A response corresponding to a [`Request`]
+pub type Response = super::Response;
+
+impl Builder {
+    fn validate(&self) -> Result<(), String> {
+        check_range("temperature", self.temperature, 0.0, 1.0)?;
+        Ok(())
+    }
+}
+impl BuildRequest for Request {
+    fn build_request(&self, client: &crate::Client) -> crate::RequestBuilder {
+        let form = form(
+            &self.file,
+            &self.model,
+            &self.prompt,
+            self.response_format,
+            self.temperature,
+        );
+
+        client
+            .reqwest_client()
+            .post(format!("{}/audio/translations", client.base_url()))
+            .auth(client)
+            .multipart(form)
+    }
+}
+#[cfg_attr(not(feature = "blocking"), async_trait::async_trait)]
+impl crate::client::Request for Request {
+    type Response = Response;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Builder;
+    use crate::api::{audio::Audio, BuildRequest};
+    use crate::{Client, Model};
+
+    #[test]
+    fn multipart_request() {
+        let request = Builder::default()
+            .file(Audio::new("german.m4a".to_string(), vec![0x00, 0x00]))
+            .model(Model::from_id("whisper-1"))
+            .prompt("Translate the greeting")
+            .build()
+            .unwrap();
+
+        let built = request
+            .build_request(&Client::new("token"))
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            built.url().as_str(),
+            "https://api.openai.com/v1/audio/translations"
+        );
+        assert!(built.headers()["content-type"]
+            .to_str()
+            .unwrap()
+            .starts_with("multipart/form-data"));
+        assert!(Builder::default()
+            .file(Audio::new("german.m4a".to_string(), vec![]))
+            .model(Model::from_id("whisper-1"))
+            .temperature(1.5)
+            .build()
+            .is_err());
+    }
+}
@@ -0,0 +1,657 @@
+//! Create chat completions for a conversation
+//! # Builder
+//! Use the [`chat::Builder`][struct@Builder] to construct a [`chat::Request`][Request] struct
+use std::collections::HashMap;
+
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use crate::{into_vec::IntoVec, model::Model};
+
+use super::{check_logit_bias, check_range, user_hash, ObjectType, RequestInfo, Usage};
+/// Create chat completions for a conversation
+///
+/// # OpenAi documentation
+/// Given a list of messages comprising a conversation, the model will return a response.
+/// # Example
+/// ```
+/// # use gpt3_rs::{api::chat::{self, Message}, Model};
+/// let request = chat::Builder::default()
+///     .model(Model::from_id("gpt-3.5-turbo"))
+///     .messages(vec![
+///         Message::system("You are a helpful assistant."),
+///         Message::user("Hello!"),
+///     ])
+///     .build()
+///     .unwrap();
+/// ```
+/// # Required
+/// ```ignore
+/// model, messages
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Builder)]
+#[builder_struct_attr(doc = "# Required")]
+#[builder_struct_attr(doc = "[`model`][Self::model()]")]
+#[builder_struct_attr(doc = "[`messages`][Self::messages()]")]
+#[builder_struct_attr(doc = "")]
+#[builder(name = "Builder", build_fn(validate = "Self::validate"))]
+pub struct Request {
+    /// ID of the model to use, e.g. `gpt-3.5-turbo`
+    pub model: Model,
+    /// A list of messages comprising the conversation so far.
+    pub messages: Vec<Message>,
+    /// What sampling temperature to use, between 0 and 2. Higher values like 0.8 will make the output more random,
+    /// while lower values like 0.2 will make it more focused and deterministic.
+    /// We generally recommend altering this or top_p but not both.
+    /// # Default
+    /// Defaults to 1.0
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+    /// An alternative to sampling with temperature, called nucleus sampling, where the model considers the results of the tokens with top_p probability mass.
+    /// So 0.1 means only the tokens comprising the top 10% probability mass are considered.
+    /// We generally recommend altering this or temperature but not both.
+    /// # Default
+    /// Defaults to 1.0
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f64>,
+    /// How many chat completion choices to generate for each input message.
+    /// # Default
+    /// Defaults to 1
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n: Option<u64>,
+    /// Up to 4 sequences where the API will stop generating further tokens.
+    #[builder(default, setter(strip_option, into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop: Option<IntoVec<String>>,
+    /// The maximum number of tokens to generate in the chat completion.
+    /// The total length of input tokens and generated tokens is limited by the model's context length.
+    /// # Default
+    /// Defaults to the remaining context of the model
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u64>,
+    /// If set, partial message deltas will be sent, like in ChatGPT.
+    /// Tokens will be sent as data-only server-sent events as they become available, with the stream terminated by a data: [DONE] message.
+    ///
+    /// Use [`Client::request_stream`](crate::Client::request_stream) instead of setting this.
+    /// # Default
+    /// Defaults to false
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
+    /// Number between -2.0 and 2.0. Positive values penalize new tokens based on whether they appear in the text so far, increasing the model's likelihood to talk about new topics.
+    ///
+    /// [See more information about frequency and presence penalties](https://beta.openai.com/docs/api-reference/parameter-details)
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub presence_penalty: Option<f64>,
+    /// Number between -2.0 and 2.0. Positive values penalize new tokens based on their existing frequency in the text so far, decreasing the model's likelihood to repeat the same line verbatim.
+    ///
+    /// [See more information about frequency and presence penalties](https://beta.openai.com/docs/api-reference/parameter-details)
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frequency_penalty: Option<f64>,
+    /// Modify the likelihood of specified tokens appearing in the completion.
+    ///
+    /// Accepts a json object that maps tokens (specified by their token ID in the tokenizer) to an associated bias value from -100 to 100.
+    /// Mathematically, the bias is added to the logits generated by the model prior to sampling.
+    /// The exact effect will vary per model, but values between -1 and 1 should decrease or increase likelihood of selection;
+    /// values like -100 or 100 should result in a ban or exclusive selection of the relevant token.
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logit_bias: Option<HashMap<String, i16>>,
+    /// A list of tools the model may call. Currently, only functions are supported as a tool.
+    /// Use this to provide a list of functions the model may generate JSON inputs for.
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<Tool>>,
+    /// Controls which (if any) function is called by the model.
+    /// # Default
+    /// Defaults to [`ToolChoice::None`] when no tools are present and [`ToolChoice::Auto`] if tools are present
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<ToolChoice>,
+    /// A unique identifier representing your end-user, which will help OpenAI to monitor and detect abuse.
+    /// It should not contain identifiable information, see [`Builder::user_hash`].
+    #[builder(default, setter(strip_option, into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+}
+/// The author of a [`Message`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    /// Instructions that set the behavior of the assistant
+    System,
+    /// A message of the user
+    User,
+    /// A message of the model, either generated or written as an example
+    Assistant,
+    /// The result of a [`ToolCall`] of the assistant
+    Tool,
+}
+/// A message of the conversation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Message {
+    /// The role of the author of this message
+    pub role: Role,
+    /// The contents of the message,
+    /// empty for assistant messages that only contain [`tool_calls`](Self::tool_calls)
+    #[serde(default, deserialize_with = "nullable")]
+    pub content: String,
+    /// The tools the assistant called instead of answering
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_calls: Option<Vec<ToolCall>>,
+    /// The id of the [`ToolCall`] a [`Role::Tool`] message responds to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_call_id: Option<String>,
+}
+/// Deserializes the `null` content of a message as an empty string
+fn nullable<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    Ok(Option::<String>::deserialize(deserializer)?.unwrap_or_default())
+}
+impl Message {
+    /// Creates a message with the given role
+    pub fn new(role: Role, content: impl Into<String>) -> Self {
+        Message {
+            role,
+            content: content.into(),
+            tool_calls: None,
+            tool_call_id: None,
+        }
+    }
+    /// Creates a [`Role::Tool`] message with the result of a tool call
+    /// # Example
+    /// ```ignore
+    /// let call = &response.tool_calls()[0];
+    /// let weather = get_weather(call.function.parse_arguments()?);
+    /// request.messages.push(response.choices[0].message.clone());
+    /// request.messages.push(Message::tool(&call.id, weather));
+    /// ```
+    pub fn tool(tool_call_id: impl Into<String>, content: impl Into<String>) -> Self {
+        Message {
+            tool_call_id: Some(tool_call_id.into()),
+            ..Message::new(Role::Tool, content)
+        }
+    }
+    /// Creates a [`Role::System`] message
+    pub fn system(content: impl Into<String>) -> Self {
+        Message::new(Role::System, content)
+    }
+    /// Creates a [`Role::User`] message
+    pub fn user(content: impl Into<String>) -> Self {
+        Message::new(Role::User, content)
+    }
+    /// Creates a [`Role::Assistant`] message
+    pub fn assistant(content: impl Into<String>) -> Self {
+        Message::new(Role::Assistant, content)
+    }
+}
+/// The type of a [`Tool`], only functions are supported
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolType {
+    #[default]
+    Function,
+}
+/// A tool the model may call, see [`Request::tools`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Tool {
+    /// The type of the tool
+    #[serde(rename = "type")]
+    pub kind: ToolType,
+    /// The function the model may call
+    pub function: FunctionDefinition,
+}
+impl Tool {
+    /// Creates a function tool
+    /// # Example
+    /// ```
+    /// # use gpt3_rs::api::chat::Tool;
+    /// let tool = Tool::function(
+    ///     "get_current_weather",
+    ///     "Get the current weather in a given location",
+    ///     serde_json::json!({
+    ///         "type": "object",
+    ///         "properties": {"location": {"type": "string"}},
+    ///         "required": ["location"]
+    ///     }),
+    /// );
+    /// ```
+    pub fn function(
+        name: impl Into<String>,
+        description: impl Into<String>,
+        parameters: serde_json::Value,
+    ) -> Self {
+        Tool {
+            kind: ToolType::Function,
+            function: FunctionDefinition {
+                name: name.into(),
+                description: Some(description.into()),
+                parameters: Some(parameters),
+            },
+        }
+    }
+}
+/// A function the model may call
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FunctionDefinition {
+    /// The name of the function to be called.
+    /// Must be a-z, A-Z, 0-9, or contain underscores and dashes, with a maximum length of 64.
+    pub name: String,
+    /// A description of what the function does, used by the model to choose when and how to call the function.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The parameters the functions accepts, described as a JSON Schema object.
+    /// Omitting `parameters` defines a function with an empty parameter list.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<serde_json::Value>,
+}
+/// Which tool the model calls, see [`Request::tool_choice`]
+///
+/// Serialized as `"none"`, `"auto"` or `{"type": "function", "function": {"name": "my_function"}}`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ToolChoice {
+    /// The model will not call a function and instead generates a message
+    None,
+    /// The model can pick between generating a message or calling a function
+    Auto,
+    /// Forces the model to call the function with this name
+    Function(String),
+}
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum ToolChoiceRepr {
+    Mode(String),
+    Function {
+        #[serde(rename = "type")]
+        kind: ToolType,
+        function: FunctionName,
+    },
+}
+#[derive(Serialize, Deserialize)]
+struct FunctionName {
+    name: String,
+}
+impl Serialize for ToolChoice {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            ToolChoice::None => serializer.serialize_str("none"),
+            ToolChoice::Auto => serializer.serialize_str("auto"),
+            ToolChoice::Function(name) => ToolChoiceRepr::Function {
+                kind: ToolType::Function,
+                function: FunctionName { name: name.clone() },
+            }
+            .serialize(serializer),
+        }
+    }
+}
+impl<'de> Deserialize<'de> for ToolChoice {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match ToolChoiceRepr::deserialize(deserializer)? {
+            ToolChoiceRepr::Mode(mode) if mode == "none" => Ok(ToolChoice::None),
+            ToolChoiceRepr::Mode(mode) if mode == "auto" => Ok(ToolChoice::Auto),
+            ToolChoiceRepr::Mode(mode) => {
+                Err(serde::de::Error::unknown_variant(&mode, &["none", "auto"]))
+            }
+            ToolChoiceRepr::Function { function, .. } => Ok(ToolChoice::Function(function.name)),
+        }
+    }
+}
+/// A call of a tool, returned in [`Message::tool_calls`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolCall {
+    /// The id of the tool call, referenced by the [`Message::tool`] with its result
+    pub id: String,
+    /// The type of the tool
+    #[serde(rename = "type")]
+    pub kind: ToolType,
+    /// The function that the model called
+    pub function: FunctionCall,
+}
+/// The function of a [`ToolCall`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FunctionCall {
+    /// The name of the function to call
+    pub name: String,
+    /// The arguments to call the function with, as generated by the model in JSON format.
+    /// Note that the model does not always generate valid JSON,
+    /// and may hallucinate parameters not defined by your function schema.
+    pub arguments: String,
+}
+impl FunctionCall {
+    /// Parses the [`arguments`](Self::arguments), e.g. into a [`serde_json::Value`]
+    ///
+    /// Fails if the model didn't generate valid JSON matching `T`.
+    pub fn parse_arguments<T: serde::de::DeserializeOwned>(&self) -> serde_json::Result<T> {
+        serde_json::from_str(&self.arguments)
+    }
+}
+/// A response corresponding to a [`Request`]
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Response {
+    /// chat completion id, e.g. `chatcmpl-123`, useful to correlate responses with logs
+    pub id: String,
+    /// The requested action
+    pub object: ObjectType,
+    /// The unix timestamp (in seconds) of when the chat completion was created
+    pub created: u64,
+    /// The model used to create the chat completion
+    pub model: String,
+    /// The answers created by this request
+    pub choices: Vec<ChatChoice>,
+    /// The tokens consumed by this request
+    pub usage: Option<Usage>,
+    /// Fields returned by the api that aren't modeled by this crate yet
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatChoice {
+    /// The index of this choice
+    pub index: usize,
+    /// The message generated by the model
+    pub message: Message,
+    /// reason why the model finished, e.g. `stop` or `length` if the message was truncated by `max_tokens`
+    pub finish_reason: Option<String>,
+}
+impl Response {
+    /// The content of the message of the first choice, `None` if there are no choices
+    pub fn text(&self) -> Option<&str> {
+        self.choices
+            .first()
+            .map(|choice| choice.message.content.as_str())
+    }
+    /// The tool calls of the message of the first choice, empty if the model didn't call a tool
+    pub fn tool_calls(&self) -> &[ToolCall] {
+        self.choices
+            .first()
+            .and_then(|choice| choice.message.tool_calls.as_deref())
+            .unwrap_or_default()
+    }
+}
+/// A part of a streamed [`Response`]
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatChunk {
+    /// chat completion id, the same for every chunk of a response
+    pub id: String,
+    /// The requested action, `chat.completion.chunk`
+    pub object: ObjectType,
+    /// The unix timestamp (in seconds) of when the chat completion was created
+    pub created: u64,
+    /// The model used to create the chat completion
+    pub model: String,
+    /// The parts of the messages generated since the last chunk
+    pub choices: Vec<ChatStreamChoice>,
+}
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatStreamChoice {
+    /// The index of the choice this delta belongs to
+    pub index: usize,
+    /// The part of the message generated since the last chunk
+    pub delta: Delta,
+    /// reason why the model finished, only set on the last chunk of a choice
+    pub finish_reason: Option<String>,
+}
+/// A part of a streamed [`Message`]
+///
+/// The first delta of a choice only contains the role, the following ones the content.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Delta {
+    /// The role of the author of the message, only set on the first delta
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<Role>,
+    /// The content generated since the last chunk
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+}
+
+impl Builder {
+    /// Appends a message to [`messages`](Self::messages())
+    /// # Example
+    /// ```
+    /// # use gpt3_rs::{api::chat, Model};
+    /// let request = chat::Builder::default()
+    ///     .model(Model::from_id("gpt-3.5-turbo"))
+    ///     .system_message("You are a helpful assistant.")
+    ///     .user_message("Who won the world series in 2020?")
+    ///     .assistant_message("The Los Angeles Dodgers won the World Series in 2020.")
+    ///     .user_message("Where was it played?")
+    ///     .build()
+    ///     .unwrap();
+    /// # assert_eq!(request.messages.len(), 4);
+    /// ```
+    pub fn message(&mut self, message: Message) -> &mut Self {
+        self.messages.get_or_insert_with(Vec::new).push(message);
+        self
+    }
+    /// Appends a [`Role::System`] message, see [`message`](Self::message)
+    pub fn system_message(&mut self, content: impl Into<String>) -> &mut Self {
+        self.message(Message::system(content))
+    }
+    /// Appends a [`Role::User`] message, see [`message`](Self::message)
+    ///
+    /// Not to be confused with [`user`](Self::user()), the id of the end-user.
+    pub fn user_message(&mut self, content: impl Into<String>) -> &mut Self {
+        self.message(Message::user(content))
+    }
+    /// Appends a [`Role::Assistant`] message, see [`message`](Self::message)
+    pub fn assistant_message(&mut self, content: impl Into<String>) -> &mut Self {
+        self.message(Message::assistant(content))
+    }
+    user_hash!();
+    fn validate(&self) -> Result<(), String> {
+        check_range("temperature", self.temperature, 0.0, 2.0)?;
+        check_logit_bias(&self.logit_bias)?;
+        check_range("top_p", self.top_p, 0.0, 1.0)?;
+        check_range("presence_penalty", self.presence_penalty, -2.0, 2.0)?;
+        check_range("frequency_penalty", self.frequency_penalty, -2.0, 2.0)?;
+        if let Some(messages) = &self.messages {
+            if messages.is_empty() {
+                return Err("`messages` must contain at least one message".to_string());
+            }
+        }
+        Ok(())
+    }
+}
+impl RequestInfo for Request {
+    fn url(&self, base_url: &str) -> String {
+        format!("{base_url}/chat/completions")
+    }
+}
+#[cfg_attr(not(feature = "blocking"), async_trait::async_trait)]
+impl crate::client::Request for Request {
+    type Response = Response;
+}
+impl crate::client::StreamRequest for Request {
+    type Chunk = ChatChunk;
+
+    fn streaming(&self) -> Self {
+        Request {
+            stream: Some(true),
+            ..self.clone()
+        }
+    }
+    fn chunk_text(chunk: &ChatChunk) -> &str {
+        chunk
+            .choices
+            .iter()
+            .find(|choice| choice.index == 0)
+            .and_then(|choice| choice.delta.content.as_deref())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Builder, Message, Response, Role, Tool, ToolChoice};
+    use crate::{api::RequestInfo, Model};
+
+    #[test]
+    fn request_body() {
+        let request = Builder::default()
+            .model(Model::from_id("gpt-3.5-turbo"))
+            .messages(vec![
+                Message::system("You are a helpful assistant."),
+                Message::user("Hello!"),
+            ])
+            .temperature(0.2)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            serde_json::json!({
+                "model": "gpt-3.5-turbo",
+                "messages": [
+                    {"role": "system", "content": "You are a helpful assistant."},
+                    {"role": "user", "content": "Hello!"}
+                ],
+                "temperature": 0.2
+            })
+        );
+        assert_eq!(
+            request.url("https://api.openai.com/v1"),
+            "https://api.openai.com/v1/chat/completions"
+        );
+        assert!(Builder::default()
+            .model(Model::from_id("gpt-3.5-turbo"))
+            .messages(vec![])
+            .build()
+            .is_err());
+    }
+    #[test]
+    fn response() {
+        let json = r#"{
+            "id": "chatcmpl-123",
+            "object": "chat.completion",
+            "created": 1677652288,
+            "model": "gpt-3.5-turbo-0301",
+            "choices": [{
+                "index": 0,
+                "message": {"role": "assistant", "content": "\n\nHello there, how may I assist you today?"},
+                "finish_reason": "stop"
+            }],
+            "usage": {"prompt_tokens": 9, "completion_tokens": 12, "total_tokens": 21}
+        }"#;
+
+        let response: Response = serde_json::from_str(json).unwrap();
+
+        assert_eq!(response.choices[0].message.role, Role::Assistant);
+        assert_eq!(
+            response.text(),
+            Some("\n\nHello there, how may I assist you today?")
+        );
+        assert_eq!(response.usage.unwrap().total_tokens, 21);
+    }
+    #[test]
+    fn tools() {
+        let request = Builder::default()
+            .model(Model::from_id("gpt-3.5-turbo"))
+            .user_message("What is the weather like in Boston?")
+            .tools(vec![Tool::function(
+                "get_current_weather",
+                "Get the current weather in a given location",
+                serde_json::json!({
+                    "type": "object",
+                    "properties": {"location": {"type": "string"}},
+                    "required": ["location"]
+                }),
+            )])
+            .tool_choice(ToolChoice::Function("get_current_weather".to_string()))
+            .build()
+            .unwrap();
+        let json = serde_json::to_value(&request).unwrap();
+
+        assert_eq!(json["tools"][0]["type"], "function");
+        assert_eq!(json["tools"][0]["function"]["name"], "get_current_weather");
+        assert_eq!(
+            json["tool_choice"],
+            serde_json::json!({"type": "function", "function": {"name": "get_current_weather"}})
+        );
+        assert_eq!(serde_json::to_value(ToolChoice::Auto).unwrap(), "auto");
+        assert_eq!(
+            serde_json::from_value::<ToolChoice>(json["tool_choice"].clone()).unwrap(),
+            ToolChoice::Function("get_current_weather".to_string())
+        );
+        assert!(serde_json::from_str::<ToolChoice>(r#""always""#).is_err());
+
+        let json = r#"{
+            "id": "chatcmpl-abc123",
+            "object": "chat.completion",
+            "created": 1699896916,
+            "model": "gpt-3.5-turbo-0613",
+            "choices": [{
+                "index": 0,
+                "message": {
+                    "role": "assistant",
+                    "content": null,
+                    "tool_calls": [{
+                        "id": "call_abc123",
+                        "type": "function",
+                        "function": {"name": "get_current_weather", "arguments": "{\n\"location\": \"Boston, MA\"\n}"}
+                    }]
+                },
+                "finish_reason": "tool_calls"
+            }]
+        }"#;
+        let response: Response = serde_json::from_str(json).unwrap();
+
+        assert_eq!(response.text(), Some(""));
+        let call = &response.tool_calls()[0];
+        assert_eq!(call.function.name, "get_current_weather");
+        let arguments: serde_json::Value = call.function.parse_arguments().unwrap();
+        assert_eq!(arguments["location"], "Boston, MA");
+        assert_eq!(
+            serde_json::to_value(Message::tool(&call.id, "22 degrees")).unwrap(),
+            serde_json::json!({"role": "tool", "content": "22 degrees", "tool_call_id": "call_abc123"})
+        );
+    }
+    #[test]
+    fn stream() {
+        use std::sync::Arc;
+
+        use crate::{transport::MockTransport, Client};
+
+        let chunk = |delta: &str| {
+            format!(
+                r#"data: {{"id":"chatcmpl-123","object":"chat.completion.chunk","created":1677652288,"model":"gpt-3.5-turbo-0301","choices":[{{"index":0,"delta":{delta},"finish_reason":null}}]}}"#
+            )
+        };
+        let body = [
+            chunk(r#"{"role":"assistant"}"#),
+            chunk(r#"{"content":"Hello"}"#),
+            chunk(r#"{"content":" there"}"#),
+            chunk("{}"),
+            "data: [DONE]".to_string(),
+        ]
+        .join("\n\n");
+        let transport = Arc::new(
+            MockTransport::new()
+                .respond(200, body.clone())
+                .respond(200, body),
+        );
+        let client = Client::with_transport("token", transport.clone());
+        let request = Builder::default()
+            .model(Model::from_id("gpt-3.5-turbo"))
+            .messages(vec![Message::user("Hello!")])
+            .build()
+            .unwrap();
+
+        let chunks =
+            crate::test_util::collect_chunks(wait!(client.request_stream(&request)).unwrap())
+                .into_iter()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+        assert_eq!(chunks.len(), 4);
+        assert_eq!(chunks[0].choices[0].delta.role, Some(Role::Assistant));
+        assert_eq!(
+            wait!(client.request_stream_collect(&request)).unwrap(),
+            "Hello there"
+        );
+        assert_eq!(transport.requests()[0].json().unwrap()["stream"], true);
+    }
+}
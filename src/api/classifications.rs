@@ -8,9 +8,11 @@ use serde::{Deserialize, Serialize};
 
 use crate::into_vec::IntoVec;
 use crate::model::Model;
-use crate::OPENAI_URL;
 
-use super::{LogProbs, RequestInfo};
+use super::{
+    check_exclusive, check_logit_bias, check_range, user_hash, Expandable, LogProbs, ObjectType,
+    RequestInfo, Usage,
+};
 /// Classifies a query from provided context
 ///
 /// # OpenAi documentation
@@ -21,7 +23,8 @@ use super::{LogProbs, RequestInfo};
 /// Labeled examples can be provided via an uploaded file, or explicitly listed in the request using the examples parameter for quick tests and small scale use cases.
 ///
 /// # Example
-/// ```ignore
+/// ```
+/// # use gpt3_rs::{api::classifications, Model};
 /// let request = classifications::Builder::default()
 ///     .model(Model::Curie)
 ///     .search_model(Model::Ada)
@@ -37,14 +40,15 @@ use super::{LogProbs, RequestInfo};
 /// ```
 /// # Required
 /// ```ignore
-/// model, query
+/// model, query, examples or file
 /// ```
-#[derive(Debug, Clone, PartialEq, Serialize, Builder)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Builder)]
 #[builder_struct_attr(doc = "# Required")]
 #[builder_struct_attr(doc = "[`model`](Self::model())")]
 #[builder_struct_attr(doc = "[`query`](Self::query())")]
+#[builder_struct_attr(doc = "[`examples`](Self::examples()) or [`file`](Self::file())")]
 #[builder_struct_attr(doc = "")]
-#[builder(name = "Builder")]
+#[builder(name = "Builder", build_fn(validate = "Self::validate"))]
 pub struct Request {
     /// ID of the engine to use for completion. You can select one of ada, babbage, curie, or davinci.
     pub model: Model,
@@ -77,6 +81,14 @@ pub struct Request {
     #[builder(default, setter(strip_option))]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub temperature: Option<f64>,
+    /// An alternative to sampling with temperature, called nucleus sampling, where the model considers the results of the tokens with top_p probability mass.
+    /// So 0.1 means only the tokens comprising the top 10% probability mass are considered.
+    /// We generally recommend altering this or temperature but not both.
+    /// # Default
+    /// Defaults to 1.0
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f64>,
     /// Include the log probabilities on the logprobs most likely tokens, as well the chosen tokens. For example, if logprobs is 5, the API will return a list of the 5 most likely tokens.
     ///  The API will always return the logprob of the sampled token, so there may be up to logprobs+1 elements in the response.
     /// The maximum value for logprobs is 5. If you need more than this, please contact support@openai.com and describe your use case.
@@ -98,7 +110,7 @@ pub struct Request {
     /// As an example, you can pass {"50256": -100} to prevent the <|endoftext|> token from being generated.
     #[builder(default, setter(strip_option))]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub logit_bias: Option<HashMap<String, i8>>,
+    pub logit_bias: Option<HashMap<String, i16>>,
     /// If set to true, the returned JSON will include a "prompt" field containing the final prompt that was used to request a completion.
     /// This is mainly useful for debugging purposes.
     #[builder(default, setter(strip_option))]
@@ -115,6 +127,7 @@ pub struct Request {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub expand: Option<IntoVec<String>>,
     /// A unique identifier representing your end-user, which will help OpenAI to monitor and detect abuse.
+    /// It should not contain identifiable information, see [`Builder::user_hash`].
     #[builder(default, setter(strip_option, into))]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub user: Option<String>,
@@ -123,18 +136,24 @@ pub struct Request {
 /// A response corresponding to a [`Request`]
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Response {
-    /// completion id
-    pub completion: String,
+    /// The completion used to create the response,
+    /// only its id unless `completion` is added to [`expand`](Request::expand)
+    pub completion: Expandable<super::completions::Response>,
     /// The chosen label for the query
     pub label: String,
     /// The model used for the completion of the request
     pub model: String,
     /// The requested action
-    pub object: String,
+    pub object: ObjectType,
     /// The model used for the search
     pub search_model: String,
     /// The examples used to judge the query
     pub selected_examples: Vec<SelectedExample>,
+    /// The tokens consumed by this request
+    pub usage: Option<Usage>,
+    /// Fields returned by the api that aren't modeled by this crate yet
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -146,15 +165,281 @@ pub struct SelectedExample {
     /// The text of the example
     pub text: String,
     /// A list of the n most likely tokens
-    pub logpropbs: Option<LogProbs>,
+    pub logprobs: Option<LogProbs>,
+}
+impl SelectedExample {
+    /// The log probability of the example, the sum of the log probabilities of its tokens
+    ///
+    /// `None` if `logprobs` weren't requested.
+    pub fn logprob(&self) -> Option<f64> {
+        let logprobs = self.logprobs.as_ref()?;
+        Some(logprobs.token_logprobs.iter().flatten().sum())
+    }
+}
+impl Response {
+    /// The selected examples, the most likely first
+    ///
+    /// Examples without [`logprob`](SelectedExample::logprob) keep their original order after the others,
+    /// so the original order is returned if `logprobs` weren't requested.
+    pub fn ranked_examples(&self) -> Vec<&SelectedExample> {
+        let mut examples = self.selected_examples.iter().collect::<Vec<_>>();
+        examples.sort_by(|a, b| match (a.logprob(), b.logprob()) {
+            (Some(a), Some(b)) => b.total_cmp(&a),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        });
+        examples
+    }
 }
 
+/// The number of examples ranked by the api if `max_examples` isn't set
+const DEFAULT_MAX_EXAMPLES: u64 = 200;
+
+#[cfg(feature = "tokenizer")]
+impl Builder {
+    /// Sets the bias of every token of `text`, see [`logit_bias`](Self::logit_bias())
+    ///
+    /// The text is tokenized with the encoding of the [`model`](Self::model()), so the model should be set first.
+    /// # Example
+    /// ```ignore
+    /// let request = classifications::Builder::default()
+    ///     .model(Model::Curie)
+    ///     .bias_token(" banana", -100)
+    ///     ...
+    /// ```
+    pub fn bias_token(&mut self, text: &str, value: i16) -> &mut Self {
+        crate::tokenizer::bias_tokens(&mut self.logit_bias, self.model.as_ref(), text, value);
+        self
+    }
+}
+
+impl Builder {
+    user_hash!();
+    fn validate(&self) -> Result<(), String> {
+        check_range("temperature", self.temperature, 0.0, 2.0)?;
+        check_logit_bias(&self.logit_bias)?;
+        check_range("top_p", self.top_p, 0.0, 1.0)?;
+        check_range("logprobs", self.logprobs, 0, 5)?;
+        check_exclusive(
+            ("examples", matches!(self.examples, Some(Some(_)))),
+            ("file", matches!(self.file, Some(Some(_)))),
+            true,
+        )?;
+        Ok(())
+    }
+    /// Builds the request like [`build`](Self::build), but fails if [`max_examples`](Self::max_examples())
+    /// is above `cost_guard`, which guards against accidentally expensive requests
+    ///
+    /// Only requests with a [`file`](Self::file()) are checked, the api ranks 200 of its examples if `max_examples` isn't set.
+    /// # Example
+    /// ```ignore
+    /// let request = classifications::Builder::default()
+    ///     .model(Model::Curie)
+    ///     .query("It is a rainy day :(")
+    ///     .file("file-123")
+    ///     .max_examples(50)
+    ///     .build_with_cost_guard(100)?;
+    /// ```
+    pub fn build_with_cost_guard(&self, cost_guard: u64) -> Result<Request, BuilderError> {
+        if let Some(Some(_)) = self.file {
+            let max_examples = self.max_examples.flatten().unwrap_or(DEFAULT_MAX_EXAMPLES);
+            if max_examples > cost_guard {
+                return Err(BuilderError::ValidationError(format!(
+                    "`max_examples` is {max_examples}, above the `cost_guard` of {cost_guard}"
+                )));
+            }
+        }
+        self.build()
+    }
+}
 impl RequestInfo for Request {
-    fn url(&self) -> String {
-        format!("{OPENAI_URL}/classifications")
+    fn url(&self, base_url: &str) -> String {
+        format!("{base_url}/classifications")
     }
 }
 #[cfg_attr(not(feature = "blocking"), async_trait::async_trait)]
 impl crate::client::Request for Request {
     type Response = Response;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Builder, Request, Response, SelectedExample};
+    use crate::api::{Expandable, LogProbs};
+    use crate::Model;
+
+    #[test]
+    fn round_trip() {
+        let request = Builder::default()
+            .model(Model::Curie)
+            .search_model(Model::Ada)
+            .query("It is a rainy day :(")
+            .examples(&[&["A happy moment", "Positive"], &["I am sad.", "Negative"]])
+            .labels(["Positive", "Negative"])
+            .build()
+            .unwrap();
+
+        let json = serde_json::to_string(&request).unwrap();
+        let loaded: Request = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(loaded, request);
+    }
+    #[test]
+    fn examples_or_file() {
+        let builder = || {
+            let mut builder = Builder::default();
+            builder.model(Model::Curie).query("It is a rainy day :(");
+            builder
+        };
+
+        assert_eq!(
+            builder().build().unwrap_err().to_string(),
+            "one of `examples` and `file` must be set"
+        );
+        assert_eq!(
+            builder()
+                .file("file-123")
+                .examples(&[&["A happy moment", "Positive"]])
+                .build()
+                .unwrap_err()
+                .to_string(),
+            "only one of `examples` and `file` can be set"
+        );
+        assert!(builder().file("file-123").build().is_ok());
+    }
+    #[test]
+    fn cost_guard() {
+        let builder = || {
+            let mut builder = Builder::default();
+            builder
+                .model(Model::Curie)
+                .query("It is a rainy day :(")
+                .file("file-123");
+            builder
+        };
+
+        assert!(builder()
+            .max_examples(50)
+            .build_with_cost_guard(100)
+            .is_ok());
+        assert_eq!(
+            builder()
+                .max_examples(300)
+                .build_with_cost_guard(100)
+                .unwrap_err()
+                .to_string(),
+            "`max_examples` is 300, above the `cost_guard` of 100"
+        );
+        // the api ranks 200 examples by default
+        assert!(builder().build_with_cost_guard(100).is_err());
+        // `max_examples` doesn't apply to inline examples
+        assert!(Builder::default()
+            .model(Model::Curie)
+            .query("It is a rainy day :(")
+            .examples(&[&["A happy moment", "Positive"]])
+            .build_with_cost_guard(100)
+            .is_ok());
+    }
+    #[test]
+    fn top_p_only_when_set() {
+        let builder = || {
+            let mut builder = Builder::default();
+            builder
+                .model(Model::Curie)
+                .query("It is a rainy day :(")
+                .file("file-123");
+            builder
+        };
+
+        let json = serde_json::to_value(builder().build().unwrap()).unwrap();
+        assert!(json.get("top_p").is_none());
+
+        let json = serde_json::to_value(builder().top_p(0.1).build().unwrap()).unwrap();
+        assert_eq!(json["top_p"], 0.1);
+        assert!(builder().top_p(1.5).build().is_err());
+    }
+
+    #[test]
+    fn expanded_completion() {
+        let response = |completion: &str| {
+            serde_json::from_str::<Response>(&format!(
+                r#"{{"completion": {completion}, "label": "Negative", "model": "curie:2020-05-03", "object": "classification", "search_model": "ada", "selected_examples": []}}"#
+            ))
+            .unwrap()
+        };
+
+        let id = response(r#""cmpl-2euN7lUVZ0d4RKbQqRV79IiiE6M1f""#);
+        assert_eq!(
+            id.completion,
+            Expandable::Id("cmpl-2euN7lUVZ0d4RKbQqRV79IiiE6M1f".to_string())
+        );
+        assert!(id.completion.expanded().is_none());
+
+        let expanded = response(
+            r#"{"id": "cmpl-2euN7lUVZ0d4RKbQqRV79IiiE6M1f", "object": "text_completion", "created": 1, "model": "curie:2020-05-03", "choices": [{"text": " Negative", "index": 0, "logprobs": null, "finish_reason": "length"}]}"#,
+        );
+        let completion = expanded.completion.expanded().unwrap();
+        assert_eq!(completion.id, "cmpl-2euN7lUVZ0d4RKbQqRV79IiiE6M1f");
+        assert_eq!(completion.choices[0].text, " Negative");
+    }
+    #[test]
+    fn ranked_examples() {
+        let example = |document, logprobs: Option<&[f64]>| SelectedExample {
+            document,
+            logprobs: logprobs.map(|logprobs| LogProbs {
+                token_logprobs: logprobs.iter().copied().map(Some).collect(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let mut response = Response {
+            selected_examples: vec![
+                example(0, Some(&[-1.0, -2.0])),
+                example(1, None),
+                example(2, Some(&[-0.5])),
+                example(3, Some(&[-1.0, -0.5])),
+            ],
+            ..Default::default()
+        };
+
+        let ranked = |response: &Response| {
+            response
+                .ranked_examples()
+                .iter()
+                .map(|example| example.document)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ranked(&response), [2, 3, 0, 1]);
+
+        response
+            .selected_examples
+            .iter_mut()
+            .for_each(|example| example.logprobs = None);
+        assert_eq!(ranked(&response), [0, 1, 2, 3]);
+    }
+    #[test]
+    fn unknown_fields_are_kept() {
+        let json = r#"{
+            "completion": "cmpl-2euN7lUVZ0d4RKbQqRV79IiiE6M1f",
+            "label": "Negative",
+            "model": "curie:2020-05-03",
+            "object": "classification",
+            "search_model": "ada",
+            "selected_examples": [
+                {"document": 1, "label": "Negative", "text": "I am sad."}
+            ],
+            "prompt": "Please classify a piece of text into the following categories: Positive, Negative"
+        }"#;
+
+        let response: Response = serde_json::from_str(json).unwrap();
+
+        assert_eq!(response.label, "Negative");
+        assert_eq!(response.selected_examples[0].logprobs, None);
+        assert_eq!(
+            response.extra["prompt"],
+            "Please classify a piece of text into the following categories: Positive, Negative"
+        );
+        assert_eq!(response.extra.len(), 1);
+    }
+}
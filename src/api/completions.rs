@@ -6,15 +6,20 @@ use std::collections::HashMap;
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
-use crate::{into_vec::IntoVec, model::Model};
+use crate::{
+    into_vec::IntoVec,
+    model::Model,
+    template::{Template, TemplateError},
+};
 
-use super::{LogProbs, RequestInfo};
+use super::{check_logit_bias, check_range, user_hash, LogProbs, ObjectType, RequestInfo, Usage};
 /// Create completions for a prompt
 ///
 /// # OpenAi documentation
 /// Given a prompt, the model will return one or more predicted completions, and can also return the probabilities of alternative tokens at each position.
 /// # Example
-/// ```ignore
+/// ```
+/// # use gpt3_rs::{api::completions, Model};
 /// let request = completions::Builder::default()
 ///     .model(Model::Curie)
 ///     .prompt(&["Say this is a test"])
@@ -30,17 +35,21 @@ use super::{LogProbs, RequestInfo};
 /// ```ignore
 /// model
 /// ```
-#[derive(Debug, Clone, PartialEq, Serialize, Builder)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Builder)]
 #[builder_struct_attr(doc = "# Required")]
 #[builder_struct_attr(doc = "[`model`][Self::model()]")]
 #[builder_struct_attr(doc = "")]
-#[builder(name = "Builder")]
+#[builder(name = "Builder", build_fn(validate = "Self::validate"))]
 pub struct Request {
-    #[serde(skip_serializing)]
+    /// The model is part of the url instead of the body, it's only serialized to persist the request
+    /// and deserializes to the default model if it's missing.
+    #[serde(default)]
     pub model: Model,
     /// The prompt(s) to generate completions for, encoded as a string, array of strings, array of tokens, or array of token arrays.
     /// Note that <|endoftext|> is the document separator that the model sees during training,
     /// so if a prompt is not specified the model will generate as if from the beginning of a new document.
+    ///
+    /// Multiple prompts are completed in a single request, see [`Response::choices_per_prompt`].
     #[builder(default, setter(strip_option, into))]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub prompt: Option<IntoVec<String>>,
@@ -52,7 +61,7 @@ pub struct Request {
     /// The token count of your prompt plus max_tokens cannot exceed the model's context length.
     /// Most models have a context length of 2048 tokens (except for the newest models, which support 4096).
     /// # Default
-    /// Defaults to 16
+    /// Defaults to 16 when not set, which is often too short for anything but a few words
     #[builder(default, setter(strip_option))]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_tokens: Option<u64>,
@@ -79,7 +88,7 @@ pub struct Request {
     /// Defaults to 1
     #[builder(default, setter(strip_option))]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub n: Option<i64>,
+    pub n: Option<u64>,
     /// Whether to stream back partial progress.
     /// If set, tokens will be sent as data-only server-sent events as they become available, with the stream terminated by a data: [DONE] message.
     /// # Default
@@ -94,7 +103,14 @@ pub struct Request {
     /// # Default
     /// Defaults to none
     #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub logprobs: Option<u8>,
+    /// Echo back the prompt in addition to the completion
+    /// # Default
+    /// Defaults to false
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub echo: Option<bool>,
     /// Up to 4 sequences where the API will stop generating further tokens. The returned text will not contain the stop sequence.
     #[builder(default, setter(strip_option, into))]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -112,11 +128,13 @@ pub struct Request {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub frequency_penalty: Option<f64>,
     /// Generates best_of completions server-side and returns the "best" (the one with the lowest log probability per token). Results cannot be streamed.
-    /// When used with n, best_of controls the number of candidate completions and n specifies how many to return – best_of must be greater than n.
+    /// When used with n, best_of controls the number of candidate completions and n specifies how many to return – best_of must be greater than or equal to n.
     /// Note: Because this parameter generates many completions, it can quickly consume your token quota. Use carefully and ensure that you have reasonable settings for max_tokens and stop.
+    /// # Default
+    /// Defaults to 1
     #[builder(default, setter(strip_option))]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub best_of: Option<u8>,
+    pub best_of: Option<u64>,
     /// Modify the likelihood of specified tokens appearing in the completion.
     /// Accepts a json object that maps tokens (specified by their token ID in the GPT tokenizer) to an associated bias value from -100 to 100.
     /// You can use this tokenizer tool (which works for both GPT-2 and GPT-3) to convert text to token IDs.
@@ -126,25 +144,178 @@ pub struct Request {
     /// As an example, you can pass {"50256": -100} to prevent the <|endoftext|> token from being generated.
     #[builder(default, setter(strip_option))]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub logit_bias: Option<HashMap<String, i8>>,
+    pub logit_bias: Option<HashMap<String, i16>>,
+    /// An object specifying the format that the model must output.
+    /// Setting to [`ResponseFormat::JsonObject`] enables JSON mode, which guarantees the message the model generates is valid JSON.
+    ///
+    /// When using JSON mode, you must also instruct the model to produce JSON yourself via the prompt.
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<ResponseFormat>,
+    /// If specified, our system will make a best effort to sample deterministically,
+    /// such that repeated requests with the same seed and parameters should return the same result.
+    ///
+    /// Determinism is not guaranteed, and you should refer to the [`system_fingerprint`](Response::system_fingerprint)
+    /// response parameter to monitor changes in the backend.
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<i64>,
     /// A unique identifier representing your end-user, which will help OpenAI to monitor and detect abuse.
+    /// It should not contain identifiable information, see [`Builder::user_hash`].
     #[builder(default, setter(strip_option, into))]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub user: Option<String>,
 }
+/// The format the model must output, see [`Request::response_format`]
+///
+/// Serialized as an object, e.g. `{"type": "json_object"}`
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseFormat {
+    /// Free form text
+    #[default]
+    Text,
+    /// Valid JSON
+    JsonObject,
+}
 /// A response corresponding to a [`Request`]
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Response {
-    /// completion id
+    /// completion id, e.g. `cmpl-uqkvlQyYK7bGYrRHQ0eXlWi7`, useful to correlate responses with logs
     pub id: String,
     /// The requested action
-    pub object: String,
-    /// The creation Time of the request
+    pub object: ObjectType,
+    /// The unix timestamp (in seconds) of when the completion was created
     pub created: u64,
     /// The model used to create the completion
     pub model: String,
     /// The answers created by this request
     pub choices: Vec<Choice>,
+    /// The tokens consumed by this request
+    pub usage: Option<Usage>,
+    /// This fingerprint represents the backend configuration that the model runs with.
+    /// Can be used in conjunction with the [`seed`](Request::seed) request parameter
+    /// to understand when backend changes have been made that might impact determinism.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_fingerprint: Option<String>,
+    /// Fields returned by the api that aren't modeled by this crate yet
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+impl Request {
+    /// Creates a builder to continue the completions of `response`, e.g. after they were cut off by `max_tokens`
+    ///
+    /// Each prompt of the builder is the prompt of this request followed by its first choice,
+    /// the other choices of a prompt with [`n`](Self::n) above 1 are not continued.
+    /// All other settings are kept.
+    /// # Example
+    /// ```ignore
+    /// let mut response = request.request(&client).await?;
+    /// while response.choices[0].finish_reason.as_deref() == Some("length") {
+    ///     request = request.continue_from(&response).build()?;
+    ///     response = request.request(&client).await?;
+    /// }
+    /// ```
+    pub fn continue_from(&self, response: &Response) -> Builder {
+        let n = self.n.unwrap_or(1) as usize;
+        let prompts = match &self.prompt {
+            Some(prompts) if !prompts.is_empty() => prompts.iter().map(String::as_str).collect(),
+            _ => vec![""],
+        };
+        let prompt = prompts
+            .into_iter()
+            .enumerate()
+            .map(|(index, prompt)| {
+                // the api returns `n` choices per prompt, in the order of the prompts
+                let completion = response
+                    .choices
+                    .iter()
+                    .find(|choice| choice.index == index * n)
+                    .map(|choice| choice.text.as_str())
+                    .unwrap_or_default();
+                match self.echo {
+                    // echoed completions already start with the prompt
+                    Some(true) => completion.to_string(),
+                    _ => format!("{prompt}{completion}"),
+                }
+            })
+            .collect();
+        Request {
+            prompt: Some(prompt),
+            ..self.clone()
+        }
+        .into()
+    }
+}
+impl From<Request> for Builder {
+    fn from(request: Request) -> Self {
+        // no `..`, so a new field can't be forgotten here
+        let Request {
+            model,
+            prompt,
+            suffix,
+            max_tokens,
+            temperature,
+            top_p,
+            n,
+            stream,
+            logprobs,
+            echo,
+            stop,
+            presence_penalty,
+            frequency_penalty,
+            best_of,
+            logit_bias,
+            response_format,
+            seed,
+            user,
+        } = request;
+        Builder {
+            model: Some(model),
+            prompt: Some(prompt),
+            suffix: Some(suffix),
+            max_tokens: Some(max_tokens),
+            temperature: Some(temperature),
+            top_p: Some(top_p),
+            n: Some(n),
+            stream: Some(stream),
+            logprobs: Some(logprobs),
+            echo: Some(echo),
+            stop: Some(stop),
+            presence_penalty: Some(presence_penalty),
+            frequency_penalty: Some(frequency_penalty),
+            best_of: Some(best_of),
+            logit_bias: Some(logit_bias),
+            response_format: Some(response_format),
+            seed: Some(seed),
+            user: Some(user),
+        }
+    }
+}
+impl Response {
+    /// The text of the first choice, `None` if there are no choices
+    /// # Example
+    /// ```ignore
+    /// let answer = request.request(&client).await?.text().unwrap_or_default();
+    /// ```
+    pub fn text(&self) -> Option<&str> {
+        self.choices.first().map(|choice| choice.text.as_str())
+    }
+    /// The texts of all choices, in the order the api returned them
+    pub fn texts(&self) -> Vec<&str> {
+        self.choices
+            .iter()
+            .map(|choice| choice.text.as_str())
+            .collect()
+    }
+    /// Groups the choices by the prompt they belong to, when sending multiple prompts at once
+    ///
+    /// The api returns `n` choices per prompt, in the order of the prompts.
+    /// # Panics
+    /// Panics if `n` is 0
+    pub fn choices_per_prompt(&self, n: usize) -> std::slice::Chunks<'_, Choice> {
+        self.choices.chunks(n)
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -155,16 +326,729 @@ pub struct Choice {
     pub index: usize,
     /// A list of the n most likely tokens
     pub logprobs: Option<LogProbs>,
-    /// reason why the model finished
-    pub finish_reason: String,
+    /// reason why the model finished, e.g. `stop` or `length` if the completion was truncated by `max_tokens`
+    pub finish_reason: Option<String>,
 }
 
+/// A part of a streamed [`Response`]
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Chunk {
+    /// completion id, e.g. `cmpl-uqkvlQyYK7bGYrRHQ0eXlWi7`, useful to correlate responses with logs
+    pub id: String,
+    /// The requested action
+    pub object: ObjectType,
+    /// The unix timestamp (in seconds) of when the completion was created
+    pub created: u64,
+    /// The model used to create the completion
+    pub model: String,
+    /// The tokens generated since the last chunk
+    pub choices: Vec<StreamChoice>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StreamChoice {
+    /// The text generated since the last chunk
+    pub text: String,
+    /// The index of the choice this text belongs to
+    pub index: usize,
+    /// A list of the n most likely tokens
+    pub logprobs: Option<LogProbs>,
+    /// reason why the model finished, only set on the last chunk of a choice
+    pub finish_reason: Option<String>,
+}
+
+#[cfg(feature = "tokenizer")]
+impl Builder {
+    /// Sets the bias of every token of `text`, see [`logit_bias`](Self::logit_bias())
+    ///
+    /// The text is tokenized with the encoding of the [`model`](Self::model()), so the model should be set first.
+    /// # Example
+    /// ```ignore
+    /// let request = completions::Builder::default()
+    ///     .model(Model::Curie)
+    ///     .bias_token(" banana", -100)
+    ///     ...
+    /// ```
+    pub fn bias_token(&mut self, text: &str, value: i16) -> &mut Self {
+        crate::tokenizer::bias_tokens(&mut self.logit_bias, self.model.as_ref(), text, value);
+        self
+    }
+}
+
+impl Builder {
+    /// Sets a single [`prompt`](Self::prompt()) made of the lines joined with newlines
+    /// # Example
+    /// ```
+    /// # use gpt3_rs::{api::completions, Model};
+    /// let examples = [("cat", "Katze"), ("dog", "Hund")];
+    /// let request = completions::Builder::default()
+    ///     .model(Model::Curie)
+    ///     .prompt_lines(
+    ///         examples
+    ///             .iter()
+    ///             .map(|(english, german)| format!("{english} => {german}"))
+    ///             .chain(["bird =>".to_string()]),
+    ///     )
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(request.prompt.unwrap()[0], "cat => Katze\ndog => Hund\nbird =>");
+    /// ```
+    pub fn prompt_lines<I>(&mut self, lines: I) -> &mut Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let lines = lines
+            .into_iter()
+            .map(|line| line.as_ref().to_owned())
+            .collect::<Vec<_>>();
+        self.prompt(lines.join("\n"))
+    }
+    /// Prepends few-shot examples to the [`prompt`](Self::prompt()), separated by an empty line
+    ///
+    /// Each example is its input followed by its output on the next line,
+    /// the prompt should be set first, see [`with_examples_separated`](Self::with_examples_separated).
+    /// # Example
+    /// ```
+    /// # use gpt3_rs::{api::completions, Model};
+    /// let request = completions::Builder::default()
+    ///     .model(Model::Curie)
+    ///     .prompt("English: bird\nGerman:")
+    ///     .with_examples(&[("English: cat", "German: Katze"), ("English: dog", "German: Hund")])
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     request.prompt.unwrap()[0],
+    ///     "English: cat\nGerman: Katze\n\nEnglish: dog\nGerman: Hund\n\nEnglish: bird\nGerman:"
+    /// );
+    /// ```
+    pub fn with_examples(&mut self, examples: &[(&str, &str)]) -> &mut Self {
+        self.with_examples_separated(examples, "\n\n")
+    }
+    /// Prepends few-shot examples to the [`prompt`](Self::prompt()), separated by `separator`
+    ///
+    /// The examples are prepended to every prompt, without a prompt they become the prompt.
+    pub fn with_examples_separated(
+        &mut self,
+        examples: &[(&str, &str)],
+        separator: &str,
+    ) -> &mut Self {
+        if examples.is_empty() {
+            return self;
+        }
+        let examples = examples
+            .iter()
+            .map(|(input, output)| format!("{input}\n{output}"))
+            .collect::<Vec<_>>()
+            .join(separator);
+        let prompt: IntoVec<String> = match self.prompt.take().flatten() {
+            Some(prompts) => prompts
+                .iter()
+                .map(|prompt| format!("{examples}{separator}{prompt}"))
+                .collect(),
+            None => [format!("{examples}{separator}")].into(),
+        };
+        self.prompt(prompt)
+    }
+    /// Sets the [`prompt`](Self::prompt()) to the rendered template
+    ///
+    /// Fails if a variable of the template is missing, see [`Template::render`].
+    /// # Example
+    /// ```
+    /// # use std::collections::HashMap;
+    /// # use gpt3_rs::{api::completions, Model, Template};
+    /// let template = Template::new("Translate {text} to {language}:");
+    /// let request = completions::Builder::default()
+    ///     .model(Model::Davinci003)
+    ///     .prompt_template(&template, &HashMap::from([("text", "cat"), ("language", "German")]))
+    ///     .unwrap()
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(request.prompt.unwrap()[0], "Translate cat to German:");
+    /// ```
+    pub fn prompt_template(
+        &mut self,
+        template: &Template,
+        vars: &HashMap<&str, &str>,
+    ) -> Result<&mut Self, TemplateError> {
+        let prompt = template.render(vars)?;
+        Ok(self.prompt(prompt))
+    }
+    fn validate(&self) -> Result<(), String> {
+        check_range("temperature", self.temperature, 0.0, 2.0)?;
+        check_logit_bias(&self.logit_bias)?;
+        check_range("top_p", self.top_p, 0.0, 1.0)?;
+        check_range("logprobs", self.logprobs, 0, 5)?;
+        Ok(())
+    }
+    /// Builds the request like [`build`](Self::build), but fails if the prompt, suffix and [`max_tokens`](Self::max_tokens())
+    /// don't fit into the [context window](Model::context_window) of the model
+    ///
+    /// The tokens are counted with the [`tokenizer`](crate::tokenizer).
+    /// # Example
+    /// ```ignore
+    /// let request = completions::Builder::default()
+    ///     .model(Model::Curie)
+    ///     .prompt(long_prompt)
+    ///     .max_tokens(100)
+    ///     .build_checked()?;
+    /// ```
+    #[cfg(feature = "tokenizer")]
+    pub fn build_checked(&self) -> Result<Request, BuilderError> {
+        self.validate_context_window()?;
+        self.build()
+    }
+    #[cfg(feature = "tokenizer")]
+    fn validate_context_window(&self) -> Result<(), String> {
+        use crate::tokenizer::count_tokens;
+
+        let model = self.model.clone().unwrap_or_default();
+        let count = |text: &Option<Option<String>>| match text {
+            Some(Some(text)) => count_tokens(&model, text),
+            _ => 0,
+        };
+        let prompt_tokens = match &self.prompt {
+            Some(Some(prompts)) => prompts
+                .iter()
+                .map(|prompt| count_tokens(&model, prompt))
+                .max()
+                .unwrap_or_default(),
+            _ => 0,
+        };
+        let max_tokens = self
+            .max_tokens
+            .flatten()
+            .map_or(DEFAULT_MAX_TOKENS, |max_tokens| max_tokens as usize);
+        let prompt_tokens = prompt_tokens + count(&self.suffix);
+        if prompt_tokens + max_tokens > model.context_window() {
+            return Err(format!(
+                "the prompt ({prompt_tokens} tokens) and `max_tokens` ({max_tokens}) don't fit into the context window of {model} ({} tokens)",
+                model.context_window()
+            ));
+        }
+        Ok(())
+    }
+}
+/// The `max_tokens` used by the api if it isn't set
+#[cfg(feature = "tokenizer")]
+const DEFAULT_MAX_TOKENS: usize = 16;
+
+impl Builder {
+    user_hash!();
+}
 impl RequestInfo for Request {
-    fn url(&self) -> String {
-        self.model.url("/completions")
+    fn url(&self, base_url: &str) -> String {
+        self.model.url(base_url, "/completions")
+    }
+    fn model_in_url(&self) -> bool {
+        true
     }
 }
 #[cfg_attr(not(feature = "blocking"), async_trait::async_trait)]
 impl crate::client::Request for Request {
     type Response = Response;
+
+    fn moderation_input(&self) -> Option<&IntoVec<String>> {
+        self.prompt.as_ref()
+    }
+}
+impl crate::client::StreamRequest for Request {
+    type Chunk = Chunk;
+
+    fn streaming(&self) -> Self {
+        Request {
+            stream: Some(true),
+            ..self.clone()
+        }
+    }
+    fn chunk_text(chunk: &Chunk) -> &str {
+        chunk
+            .choices
+            .iter()
+            .find(|choice| choice.index == 0)
+            .map_or("", |choice| choice.text.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{Builder, Response, ResponseFormat};
+    use crate::Model;
+
+    #[test]
+    fn with_examples() {
+        let examples = [
+            ("Tweet: I loved it", "Sentiment: Positive"),
+            ("Tweet: meh", "Sentiment: Neutral"),
+        ];
+        let prompt =
+            |builder: &mut Builder| builder.model(Model::Curie).build().unwrap().prompt.unwrap();
+
+        let prompts = prompt(
+            Builder::default()
+                .prompt(["Tweet: great", "Tweet: awful"])
+                .with_examples_separated(&examples, "\n###\n"),
+        );
+        assert_eq!(prompts.len(), 2);
+        assert_eq!(
+            prompts[1],
+            "Tweet: I loved it\nSentiment: Positive\n###\nTweet: meh\nSentiment: Neutral\n###\nTweet: awful"
+        );
+
+        let prompts = prompt(Builder::default().with_examples(&examples[..1]));
+        assert_eq!(prompts[0], "Tweet: I loved it\nSentiment: Positive\n\n");
+        let prompts = prompt(Builder::default().prompt("Tweet: great").with_examples(&[]));
+        assert_eq!(prompts[0], "Tweet: great");
+    }
+    #[test]
+    fn logit_bias_range() {
+        let build = |bias: i16| {
+            Builder::default()
+                .model(Model::Davinci003)
+                .logit_bias(HashMap::from([
+                    ("50256".to_string(), -100),
+                    ("198".to_string(), bias),
+                ]))
+                .build()
+        };
+
+        assert!(build(100).is_ok());
+        assert_eq!(
+            build(101).unwrap_err().to_string(),
+            "the `logit_bias` of token 198 must be between -100 and 100, got 101"
+        );
+        assert!(build(-127).is_err());
+    }
+    #[test]
+    fn json_mode() {
+        let request = Builder::default()
+            .model(Model::Davinci003)
+            .response_format(ResponseFormat::JsonObject)
+            .build()
+            .unwrap();
+        let json = serde_json::to_value(&request).unwrap();
+
+        assert_eq!(
+            json["response_format"],
+            serde_json::json!({"type": "json_object"})
+        );
+
+        let request = Builder::default().model(Model::Davinci003).build().unwrap();
+        let json = serde_json::to_value(&request).unwrap();
+        assert!(json.get("response_format").is_none());
+    }
+    #[test]
+    fn seed() {
+        let request = Builder::default()
+            .model(Model::Davinci003)
+            .seed(42)
+            .build()
+            .unwrap();
+        assert_eq!(serde_json::to_value(&request).unwrap()["seed"], 42);
+
+        let json = r#"{
+            "id": "cmpl-1",
+            "object": "text_completion",
+            "created": 1,
+            "model": "text-davinci-003",
+            "system_fingerprint": "fp_44709d6fcb",
+            "choices": []
+        }"#;
+        let response: Response = serde_json::from_str(json).unwrap();
+
+        assert_eq!(
+            response.system_fingerprint.as_deref(),
+            Some("fp_44709d6fcb")
+        );
+        assert!(response.extra.is_empty());
+    }
+    #[test]
+    fn texts() {
+        let json = r#"{
+            "id": "cmpl-1",
+            "object": "text_completion",
+            "created": 1,
+            "model": "text-curie-001",
+            "choices": [
+                {"text": "a", "index": 0, "logprobs": null, "finish_reason": "stop"},
+                {"text": "b", "index": 1, "logprobs": null, "finish_reason": "stop"}
+            ]
+        }"#;
+        let response: Response = serde_json::from_str(json).unwrap();
+
+        assert_eq!(response.text(), Some("a"));
+        assert_eq!(response.texts(), ["a", "b"]);
+        assert_eq!(Response::default().text(), None);
+    }
+    #[cfg(feature = "tokenizer")]
+    #[test]
+    fn build_checked() {
+        let prompt = "hello ".repeat(2000);
+        let builder = |max_tokens: u64| {
+            let mut builder = Builder::default();
+            builder
+                .model(Model::Curie)
+                .prompt(prompt.as_str())
+                .max_tokens(max_tokens);
+            builder
+        };
+
+        // not checked by `build`
+        assert!(builder(100).build().is_ok());
+        assert!(builder(40).build_checked().is_ok());
+        assert_eq!(
+            builder(100).build_checked().unwrap_err().to_string(),
+            "the prompt (2001 tokens) and `max_tokens` (100) don't fit into the context window of curie (2049 tokens)"
+        );
+    }
+    #[test]
+    fn round_trip() {
+        let request = Builder::default()
+            .model(Model::Curie)
+            .prompt("Say this is a test")
+            .max_tokens(7)
+            .stop(["\n"])
+            .build()
+            .unwrap();
+
+        let json = serde_json::to_string(&request).unwrap();
+        let loaded: super::Request = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(loaded, request);
+        let loaded: super::Request =
+            serde_json::from_str(r#"{"prompt": "Say this is a test"}"#).unwrap();
+        assert_eq!(loaded.max_tokens, None);
+        assert_eq!(loaded.model, Model::Davinci);
+
+        // the model is only part of the url of the sent request
+        let client = crate::Client::new("token".to_string());
+        let built = crate::api::BuildRequest::build_request(&request, &client)
+            .build()
+            .unwrap();
+        let body: serde_json::Value =
+            serde_json::from_slice(built.body().unwrap().as_bytes().unwrap()).unwrap();
+        assert!(body.get("model").is_none());
+        assert_eq!(body["max_tokens"], 7);
+    }
+    #[test]
+    fn echo_only_when_set() {
+        let request = Builder::default().model(Model::Curie).build().unwrap();
+        let json = serde_json::to_value(&request).unwrap();
+
+        assert!(json.get("echo").is_none());
+        assert!(json.get("logprobs").is_none());
+
+        let request = Builder::default()
+            .model(Model::Curie)
+            .echo(true)
+            .logprobs(1)
+            .build()
+            .unwrap();
+        let json = serde_json::to_value(&request).unwrap();
+
+        assert_eq!(json["echo"], true);
+        assert_eq!(json["logprobs"], 1);
+    }
+    #[test]
+    fn max_tokens_only_when_set() {
+        let request = Builder::default().model(Model::Curie).build().unwrap();
+        let json = serde_json::to_value(&request).unwrap();
+
+        assert!(json.get("max_tokens").is_none());
+
+        let request = Builder::default()
+            .model(Model::Curie)
+            .max_tokens(256)
+            .build()
+            .unwrap();
+        let json = serde_json::to_value(&request).unwrap();
+
+        assert_eq!(json["max_tokens"], 256);
+    }
+    #[test]
+    fn insertion_suffix() {
+        let request = Builder::default()
+            .model(Model::Davinci003)
+            .prompt("fn main() {\n")
+            .suffix("\n}")
+            .build()
+            .unwrap();
+        let json = serde_json::to_value(&request).unwrap();
+
+        assert_eq!(json["prompt"], serde_json::json!(["fn main() {\n"]));
+        assert_eq!(json["suffix"], "\n}");
+    }
+    #[test]
+    fn validate_ranges() {
+        let error = Builder::default()
+            .model(Model::Curie)
+            .temperature(5.0)
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "`temperature` must be between 0 and 2, got 5"
+        );
+
+        assert!(Builder::default()
+            .model(Model::Curie)
+            .top_p(1.5)
+            .build()
+            .is_err());
+        assert!(Builder::default()
+            .model(Model::Curie)
+            .logprobs(6)
+            .build()
+            .is_err());
+        assert!(Builder::default()
+            .model(Model::Curie)
+            .temperature(2.0)
+            .top_p(0.0)
+            .logprobs(5)
+            .build()
+            .is_ok());
+    }
+    #[test]
+    fn multiple_completions() {
+        let request = Builder::default()
+            .model(Model::Curie)
+            .n(3)
+            .best_of(5)
+            .build()
+            .unwrap();
+        let json = serde_json::to_value(&request).unwrap();
+
+        assert_eq!(json["n"], 3);
+        assert_eq!(json["best_of"], 5);
+
+        let json = r#"{
+            "id": "cmpl-1",
+            "object": "text_completion",
+            "created": 1,
+            "model": "text-curie-001",
+            "choices": [
+                {"text": "a", "index": 0, "logprobs": null, "finish_reason": "stop"},
+                {"text": "b", "index": 1, "logprobs": null, "finish_reason": "stop"},
+                {"text": "c", "index": 2, "logprobs": null, "finish_reason": "length"}
+            ]
+        }"#;
+        let response: Response = serde_json::from_str(json).unwrap();
+
+        assert_eq!(response.choices.len(), 3);
+        assert_eq!(response.choices[2].index, 2);
+        assert_eq!(response.choices[2].text, "c");
+    }
+    #[test]
+    fn penalties_only_when_set() {
+        let request = Builder::default().model(Model::Curie).build().unwrap();
+        let json = serde_json::to_value(&request).unwrap();
+
+        assert!(json.get("presence_penalty").is_none());
+        assert!(json.get("frequency_penalty").is_none());
+
+        let request = Builder::default()
+            .model(Model::Curie)
+            .presence_penalty(0.5)
+            .frequency_penalty(-1.5)
+            .build()
+            .unwrap();
+        let json = serde_json::to_value(&request).unwrap();
+
+        assert_eq!(json["presence_penalty"], 0.5);
+        assert_eq!(json["frequency_penalty"], -1.5);
+    }
+    #[test]
+    fn stop_sequences() {
+        let request = Builder::default()
+            .model(Model::Curie)
+            .stop(["\n"])
+            .build()
+            .unwrap();
+        let json = serde_json::to_value(&request).unwrap();
+
+        assert_eq!(json["stop"], serde_json::json!(["\n"]));
+
+        let request = Builder::default()
+            .model(Model::Curie)
+            .stop(["\n", "Q:", "A:", "###"])
+            .build()
+            .unwrap();
+        let json = serde_json::to_value(&request).unwrap();
+
+        assert_eq!(json["stop"], serde_json::json!(["\n", "Q:", "A:", "###"]));
+    }
+    #[test]
+    fn batched_prompts() {
+        let request = Builder::default()
+            .model(Model::Curie)
+            .prompt(["1 + 1 =", "2 + 2 ="])
+            .n(2)
+            .build()
+            .unwrap();
+        let json = serde_json::to_value(&request).unwrap();
+
+        assert_eq!(json["prompt"], serde_json::json!(["1 + 1 =", "2 + 2 ="]));
+
+        let json = r#"{
+            "id": "cmpl-1",
+            "object": "text_completion",
+            "created": 1,
+            "model": "text-curie-001",
+            "choices": [
+                {"text": " 2", "index": 0, "logprobs": null, "finish_reason": "stop"},
+                {"text": " two", "index": 1, "logprobs": null, "finish_reason": "stop"},
+                {"text": " 4", "index": 2, "logprobs": null, "finish_reason": "stop"},
+                {"text": " four", "index": 3, "logprobs": null, "finish_reason": "stop"}
+            ]
+        }"#;
+        let response: Response = serde_json::from_str(json).unwrap();
+        let grouped = response.choices_per_prompt(2).collect::<Vec<_>>();
+
+        assert_eq!(grouped.len(), 2);
+        assert_eq!(grouped[1][0].text, " 4");
+        assert_eq!(grouped[1][1].text, " four");
+    }
+    #[test]
+    fn user_hash() {
+        let request = Builder::default()
+            .model(Model::Curie)
+            .user_hash("user@example.com")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            request.user.as_deref(),
+            Some("b4c9a289323b21a01c3e940f150eb9b8c542587f1abfd8f0e1cc1ffc5e475514")
+        );
+    }
+    #[test]
+    fn continue_from() {
+        let request = Builder::default()
+            .model(Model::Curie)
+            .prompt("Once upon a time")
+            .max_tokens(3)
+            .temperature(0.7)
+            .build()
+            .unwrap();
+        let response = Response {
+            choices: vec![super::Choice {
+                text: " there was a".to_string(),
+                finish_reason: Some("length".to_string()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let next = request.continue_from(&response).build().unwrap();
+
+        assert_eq!(
+            next.prompt.as_deref().unwrap(),
+            ["Once upon a time there was a"]
+        );
+        assert_eq!(next.model, Model::Curie);
+        assert_eq!(next.max_tokens, Some(3));
+        assert_eq!(next.temperature, Some(0.7));
+        assert_eq!(Builder::from(request.clone()).build().unwrap(), request);
+    }
+    #[test]
+    fn continue_from_multiple_prompts() {
+        let request = Builder::default()
+            .model(Model::Curie)
+            .prompt(["Once upon a time", "In a galaxy"])
+            .n(2)
+            .build()
+            .unwrap();
+        let choice = |text: &str, index| super::Choice {
+            text: text.to_string(),
+            index,
+            ..Default::default()
+        };
+        let response = Response {
+            choices: vec![
+                choice(" far away", 3),
+                choice(" there was", 0),
+                choice(" there lived", 1),
+                choice(" far, far away", 2),
+            ],
+            ..Default::default()
+        };
+
+        let next = request.continue_from(&response).build().unwrap();
+
+        assert_eq!(
+            next.prompt.as_deref().unwrap(),
+            ["Once upon a time there was", "In a galaxy far, far away"]
+        );
+        assert_eq!(next.n, Some(2));
+    }
+    #[cfg(feature = "tokenizer")]
+    #[test]
+    fn bias_token() {
+        let request = Builder::default()
+            .model(Model::Curie)
+            .bias_token("hello world", -100)
+            .bias_token("<|endoftext|>", 10)
+            .build()
+            .unwrap();
+        let logit_bias = request.logit_bias.unwrap();
+
+        assert_eq!(logit_bias.len(), 3);
+        assert_eq!(logit_bias["31373"], -100);
+        assert_eq!(logit_bias["995"], -100);
+        assert_eq!(logit_bias["50256"], 10);
+    }
+    #[test]
+    fn response_metadata() {
+        // captured from the api
+        let json = r#"{
+            "id": "cmpl-uqkvlQyYK7bGYrRHQ0eXlWi7",
+            "object": "text_completion",
+            "created": 1589478378,
+            "model": "text-davinci-002",
+            "choices": [
+                {"text": "\n\nThis is a test", "index": 0, "logprobs": null, "finish_reason": "length"}
+            ],
+            "usage": {"prompt_tokens": 5, "completion_tokens": 6, "total_tokens": 11}
+        }"#;
+        let response: Response = serde_json::from_str(json).unwrap();
+
+        assert_eq!(response.id, "cmpl-uqkvlQyYK7bGYrRHQ0eXlWi7");
+        assert_eq!(response.object, "text_completion");
+        assert_eq!(response.created, 1589478378);
+        assert_eq!(response.model, "text-davinci-002");
+        assert!(response.extra.is_empty());
+    }
+    #[test]
+    fn deserialize_logprobs() {
+        let json = r#"{
+            "id": "cmpl-1",
+            "object": "text_completion",
+            "created": 1,
+            "model": "text-curie-001",
+            "choices": [{
+                "text": "Say this is a test",
+                "index": 0,
+                "logprobs": {
+                    "tokens": ["Say", " this"],
+                    "token_logprobs": [null, -3.5],
+                    "top_logprobs": [null, {" this": -3.5}],
+                    "text_offset": [0, 3]
+                },
+                "finish_reason": "length"
+            }]
+        }"#;
+
+        let response: Response = serde_json::from_str(json).unwrap();
+        let logprobs = response.choices[0].logprobs.as_ref().unwrap();
+
+        assert_eq!(logprobs.tokens, ["Say", " this"]);
+        assert_eq!(logprobs.token_logprobs, [None, Some(-3.5)]);
+        assert_eq!(logprobs.text_offset, [0, 3]);
+        assert_eq!(response.choices[0].finish_reason.as_deref(), Some("length"));
+    }
 }
@@ -1,12 +1,14 @@
 //!Edit text based off of an instruction
 //! # Builder
 //! Use the [`edits::Builder`][struct@Builder] to construct a [`edits::Request`][Request] struct
+use std::collections::HashMap;
+
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
-use crate::model::Model;
+use crate::model::{Model, UNSUPPORTED_EDIT_MODEL};
 
-use super::RequestInfo;
+use super::{check_range, ObjectType, RequestInfo, Usage};
 /// Edit text based off of an instruction
 ///
 /// # OpenAi documentation
@@ -14,7 +16,7 @@ use super::RequestInfo;
 /// # Example
 /// ```ignore
 /// let request = edits::Builder::default()
-///     .model(Model::Curie)
+///     .model(Model::Davinci)
 ///     .input("What day of the wek is it?")
 ///     .instruction("Fix the spelling mistakes")
 ///     .build()
@@ -24,14 +26,15 @@ use super::RequestInfo;
 /// ```ignore
 /// model
 /// ```
-#[derive(Debug, Clone, PartialEq, Serialize, Builder)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Builder)]
 #[builder_struct_attr(doc = "# Required")]
 #[builder_struct_attr(doc = "[`model`][Self::model()]")]
 #[builder_struct_attr(doc = "[`instruction`][Self::instruction()]")]
 #[builder_struct_attr(doc = "")]
-#[builder(name = "Builder")]
+#[builder(name = "Builder", build_fn(validate = "Self::validate"))]
 pub struct Request {
-    #[serde(skip_serializing)]
+    /// ID of the model to use. Only [`Model::Davinci`], [`Model::CodeDavinci002`] and [`Model::Custom`] support editing.
+    #[serde(serialize_with = "Model::serialize_edit")]
     pub model: Model,
     /// The input text to use as a starting point for the edit.
     #[builder(default, setter(strip_option, into))]
@@ -40,6 +43,12 @@ pub struct Request {
     /// The instruction that tells the model how to edit the prompt.
     #[builder(setter(into))]
     pub instruction: String,
+    /// How many edits to generate for the input and instruction.
+    /// # Default
+    /// Defaults to 1
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n: Option<u64>,
     /// What sampling temperature to use. Higher values means the model will take more risks.
     /// Try 0.9 for more creative applications, and 0 (argmax sampling) for ones with a well-defined answer.
     /// We generally recommend altering this or top_p but not both.
@@ -57,11 +66,16 @@ pub struct Request {
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Response {
     /// The requested action
-    pub object: String,
+    pub object: ObjectType,
     /// The creation Time of the request
     pub created: u64,
     /// The choices return by the model
     pub choices: Vec<Choice>,
+    /// The tokens consumed by this request
+    pub usage: Option<Usage>,
+    /// Fields returned by the api that aren't modeled by this crate yet
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
 }
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Choice {
@@ -70,12 +84,80 @@ pub struct Choice {
     /// The index of this choice
     pub index: usize,
 }
+impl Response {
+    /// The text of the first choice, `None` if there are no choices
+    pub fn text(&self) -> Option<&str> {
+        self.choices.first().map(|choice| choice.text.as_str())
+    }
+    /// The texts of all choices, in the order the api returned them
+    pub fn texts(&self) -> Vec<&str> {
+        self.choices
+            .iter()
+            .map(|choice| choice.text.as_str())
+            .collect()
+    }
+}
+impl Builder {
+    fn validate(&self) -> Result<(), String> {
+        check_range("temperature", self.temperature, 0.0, 2.0)?;
+        check_range("top_p", self.top_p, 0.0, 1.0)?;
+        if let Some(model) = &self.model {
+            if model.edit_id().is_none() {
+                return Err(UNSUPPORTED_EDIT_MODEL.to_string());
+            }
+        }
+        Ok(())
+    }
+}
 impl RequestInfo for Request {
-    fn url(&self) -> String {
-        self.model.edit_url("/edits")
+    fn url(&self, base_url: &str) -> String {
+        format!("{base_url}/edits")
     }
 }
 #[cfg_attr(not(feature = "blocking"), async_trait::async_trait)]
 impl crate::client::Request for Request {
     type Response = Response;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Builder;
+    use crate::Model;
+
+    #[test]
+    fn model_in_body() {
+        let request = Builder::default()
+            .model(Model::Davinci)
+            .instruction("Fix the spelling mistakes")
+            .n(2)
+            .build()
+            .unwrap();
+
+        let json = serde_json::to_value(&request).unwrap();
+
+        assert_eq!(json["model"], "text-davinci-edit-001");
+        assert_eq!(json["n"], 2);
+    }
+    #[test]
+    fn unsupported_model() {
+        let error = Builder::default()
+            .model(Model::Ada)
+            .instruction("Fix the spelling mistakes")
+            .build()
+            .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "editing only supports davinci, code davinci and custom models"
+        );
+        let request = super::Request {
+            model: Model::Ada,
+            ..Builder::default()
+                .model(Model::Davinci)
+                .instruction("Fix the spelling mistakes")
+                .build()
+                .unwrap()
+        };
+        assert!(serde_json::to_value(&request).is_err());
+    }
+}
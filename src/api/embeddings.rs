@@ -0,0 +1,180 @@
+//! Create embeddings for a given input
+//! # Builder
+//! Use the [`embeddings::Builder`][struct@Builder] to construct an [`embeddings::Request`][Request] struct
+use std::collections::HashMap;
+
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use crate::{into_vec::IntoVec, model::Model};
+
+use super::{user_hash, ObjectType, RequestInfo, Usage};
+/// Create embeddings for a given input
+///
+/// # OpenAi documentation
+/// Creates an embedding vector representing the input text.
+///
+/// Get a vector representation of a given input that can be easily consumed by machine learning models and algorithms.
+/// # Example
+/// ```ignore
+/// let request = embeddings::Builder::default()
+///     .model(Model::TextEmbeddingAda002)
+///     .input(&["The food was delicious and the waiter..."])
+///     .build()
+///     .unwrap();
+/// ```
+/// # Required
+/// ```ignore
+/// model, input
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Builder)]
+#[builder_struct_attr(doc = "# Required")]
+#[builder_struct_attr(doc = "[`model`][Self::model()]")]
+#[builder_struct_attr(doc = "[`input`][Self::input()]")]
+#[builder_struct_attr(doc = "")]
+#[builder(name = "Builder")]
+pub struct Request {
+    /// ID of the model to use.
+    pub model: Model,
+    /// Input text to get embeddings for, encoded as a string or array of tokens.
+    /// To get embeddings for multiple inputs in a single request, pass an array of strings or array of token arrays.
+    /// Each input must not exceed 8192 tokens in length.
+    #[builder(setter(into))]
+    pub input: IntoVec<String>,
+    /// A unique identifier representing your end-user, which will help OpenAI to monitor and detect abuse.
+    /// It should not contain identifiable information, see [`Builder::user_hash`].
+    #[builder(default, setter(strip_option, into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+}
+/// A response corresponding to a [`Request`]
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Response {
+    /// The requested action
+    pub object: ObjectType,
+    /// The embeddings created by this request, one per input
+    pub data: Vec<Embedding>,
+    /// The model used to create the embeddings
+    pub model: String,
+    /// The tokens consumed by this request
+    pub usage: Usage,
+    /// Fields returned by the api that aren't modeled by this crate yet
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Embedding {
+    /// The requested action
+    pub object: ObjectType,
+    /// The embedding vector
+    pub embedding: Vec<f32>,
+    /// The index of the input this embedding belongs to
+    pub index: usize,
+}
+
+impl Embedding {
+    /// The cosine similarity to another embedding, see [`cosine_similarity`]
+    pub fn similarity(&self, other: &Embedding) -> f32 {
+        cosine_similarity(&self.embedding, &other.embedding)
+    }
+}
+
+/// The cosine similarity of two vectors, from -1.0 (opposite) to 1.0 (same direction)
+///
+/// OpenAI embeddings are normalized to length 1, so this is the same as their dot product.
+/// Returns 0.0 if one of the vectors is all zeros.
+/// # Panics
+/// Panics if the vectors have different lengths, e.g. because they were created by different models.
+/// # Example
+/// ```
+/// # use gpt3_rs::api::embeddings::cosine_similarity;
+/// assert_eq!(cosine_similarity(&[1.0, 0.0], &[1.0, 0.0]), 1.0);
+/// assert_eq!(cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]), 0.0);
+/// ```
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    assert_eq!(a.len(), b.len(), "embeddings have different dimensions");
+    let (mut dot, mut norm_a, mut norm_b) = (0.0, 0.0, 0.0);
+    for (a, b) in a.iter().zip(b) {
+        dot += a * b;
+        norm_a += a * a;
+        norm_b += b * b;
+    }
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a.sqrt() * norm_b.sqrt())
+}
+
+/// Ranks the candidates by their similarity to the query, most similar first
+///
+/// Returns the index of each candidate with its similarity.
+/// # Example
+/// ```ignore
+/// let response = embeddings::Builder::default()
+///     .model(Model::TextEmbeddingAda002)
+///     .input(&["What is the capital of France?", "Paris is in France", "Berlin is in Germany"])
+///     .build()
+///     .unwrap()
+///     .request(&client)
+///     .await?;
+///
+/// let (query, documents) = response.data.split_first().unwrap();
+/// let (best, similarity) = embeddings::rank(query, documents)[0];
+/// ```
+pub fn rank(query: &Embedding, candidates: &[Embedding]) -> Vec<(usize, f32)> {
+    let mut ranked = candidates
+        .iter()
+        .map(|candidate| query.similarity(candidate))
+        .enumerate()
+        .collect::<Vec<_>>();
+    ranked.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+    ranked
+}
+
+impl Builder {
+    user_hash!();
+}
+impl RequestInfo for Request {
+    fn url(&self, base_url: &str) -> String {
+        format!("{base_url}/embeddings")
+    }
+}
+#[cfg_attr(not(feature = "blocking"), async_trait::async_trait)]
+impl crate::client::Request for Request {
+    type Response = Response;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cosine_similarity, rank, Embedding};
+
+    fn embedding(vector: &[f32]) -> Embedding {
+        Embedding {
+            embedding: vector.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn similarity() {
+        assert!((cosine_similarity(&[1.0, 1.0], &[2.0, 2.0]) - 1.0).abs() < 1e-6);
+        assert!((cosine_similarity(&[1.0, 0.0], &[-1.0, 0.0]) + 1.0).abs() < 1e-6);
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 0.0]), 0.0);
+    }
+    #[test]
+    fn ranks_most_similar_first() {
+        let query = embedding(&[1.0, 0.0]);
+        let candidates = [
+            embedding(&[0.0, 1.0]),
+            embedding(&[1.0, 0.1]),
+            embedding(&[-1.0, 0.0]),
+        ];
+
+        let order = rank(&query, &candidates)
+            .into_iter()
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        assert_eq!(order, [1, 0, 2]);
+    }
+}
@@ -0,0 +1,31 @@
+use crate::api::{Auth, BuildRequest, ObjectType};
+use serde::{Deserialize, Serialize};
+
+use super::Engine;
+
+/// # OpenAi documentation
+///
+/// Lists the currently available engines, and provides basic information about each one such as the owner and availability.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request;
+
+impl BuildRequest for Request {
+    fn build_request(&self, client: &crate::Client) -> crate::RequestBuilder {
+        client
+            .reqwest_client()
+            .get(format!("{}/engines", client.base_url()))
+            .auth(client)
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Response {
+    /// List of available engines
+    pub data: Vec<Engine>,
+    /// Action of the request
+    pub object: ObjectType,
+}
+#[cfg_attr(not(feature = "blocking"), async_trait::async_trait)]
+impl crate::client::Request for Request {
+    type Response = Response;
+}
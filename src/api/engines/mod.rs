@@ -0,0 +1,27 @@
+//! This module contains other modules that are used to interact with the engines api
+//!
+//! Engines are the models available for use with the api, including your fine-tuned models.
+//!  
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::ObjectType;
+
+pub mod list;
+
+/// A model available through the api
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Engine {
+    /// The engine id, usable as [`Model::Custom`](crate::Model::Custom)
+    pub id: String,
+    /// The object of the request
+    pub object: ObjectType,
+    /// The owner of the engine, e.g. `openai`
+    pub owner: String,
+    /// Whether the engine is ready to be used
+    pub ready: bool,
+    /// Fields returned by the api that aren't modeled by this crate yet
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
@@ -1,5 +1,4 @@
-use crate::api::BuildRequest;
-use crate::OPENAI_URL;
+use crate::api::{Auth, BuildRequest};
 use serde::{Deserialize, Serialize};
 
 /// # OpenAi documentation
@@ -18,8 +17,12 @@ impl BuildRequest for Request {
     fn build_request(&self, client: &crate::Client) -> crate::RequestBuilder {
         client
             .reqwest_client()
-            .get(format!("{OPENAI_URL}/files/{}/content", self.file_id))
-            .bearer_auth(client.gpt_token())
+            .get(format!(
+                "{}/files/{}/content",
+                client.base_url(),
+                self.file_id
+            ))
+            .auth(client)
     }
 }
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -35,7 +38,7 @@ impl crate::client::Request for Request {
     async fn request(
         &self,
         client: &crate::Client,
-    ) -> reqwest::Result<<Self as crate::client::Request>::Response> {
+    ) -> Result<<Self as crate::client::Request>::Response, crate::ClientError> {
         Ok(Response {
             content: self.request_raw(client).await?,
         })
@@ -44,7 +47,7 @@ impl crate::client::Request for Request {
     fn request(
         &self,
         client: &crate::Client,
-    ) -> reqwest::Result<<Self as crate::client::Request>::Response> {
+    ) -> Result<<Self as crate::client::Request>::Response, crate::ClientError> {
         Ok(Response {
             content: self.request_raw(client)?,
         })
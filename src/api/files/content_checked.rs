@@ -1,7 +1,9 @@
 use serde::Deserialize;
 
-use crate::OPENAI_URL;
-use crate::{api::BuildRequest, Client};
+use crate::{
+    api::{Auth, BuildRequest},
+    Client, ClientError,
+};
 
 use super::{Answers, Classifications, File, FineTuning, Search};
 
@@ -25,8 +27,12 @@ impl BuildRequest for Request {
     fn build_request(&self, client: &crate::Client) -> crate::RequestBuilder {
         client
             .reqwest_client()
-            .get(format!("{OPENAI_URL}/files/{}/content", self.file_id))
-            .bearer_auth(client.gpt_token())
+            .get(format!(
+                "{}/files/{}/content",
+                client.base_url(),
+                self.file_id
+            ))
+            .auth(client)
     }
 }
 #[derive(Debug, Clone, Deserialize)]
@@ -35,6 +41,10 @@ pub enum Response {
     Answers(super::File<Answers>),
     FineTuning(super::File<FineTuning>),
     Classifications(super::File<Classifications>),
+    /// The results of a fine-tune job, a csv file
+    FineTuneResults(String),
+    /// A file with a purpose this crate doesn't know, as text
+    Other(String),
 }
 
 #[cfg(not(feature = "blocking"))]
@@ -42,7 +52,7 @@ pub enum Response {
 impl crate::client::Request for crate::api::files::content_checked::Request {
     type Response = crate::api::files::content_checked::Response;
 
-    async fn request(&self, client: &Client) -> reqwest::Result<Self::Response> {
+    async fn request(&self, client: &Client) -> Result<Self::Response, ClientError> {
         let metadata = crate::api::files::metadata::Request::new(self.file_id.clone())
             .request(client)
             .await?;
@@ -51,38 +61,40 @@ impl crate::client::Request for crate::api::files::content_checked::Request {
             .request_raw(client)
             .await?;
 
-        let text = dbg!(response);
+        let text = response;
         let iter = text.lines();
 
         let file = match metadata.purpose {
             crate::prelude::Purpose::Search => Self::Response::Search(File::new(
                 metadata.filename,
                 iter.map(serde_json::from_str)
-                    .map(Result::unwrap)
-                    .collect::<Vec<Search>>(),
+                    .collect::<Result<Vec<Search>, _>>()
+                    .map_err(ClientError::Deserialize)?,
             )),
             crate::prelude::Purpose::Answers => Self::Response::Answers(File::new(
                 metadata.filename,
                 iter.map(serde_json::from_str)
-                    .map(Result::unwrap)
-                    .collect::<Vec<Answers>>(),
+                    .collect::<Result<Vec<Answers>, _>>()
+                    .map_err(ClientError::Deserialize)?,
             )),
             crate::prelude::Purpose::Classifications => Self::Response::Classifications(File::new(
                 metadata.filename,
                 iter.map(serde_json::from_str)
-                    .map(Result::unwrap)
-                    .collect::<Vec<Classifications>>(),
+                    .collect::<Result<Vec<Classifications>, _>>()
+                    .map_err(ClientError::Deserialize)?,
             )),
             crate::prelude::Purpose::FineTuning => Self::Response::FineTuning(File::new(
                 metadata.filename,
                 iter.map(serde_json::from_str)
-                    .map(Result::unwrap)
-                    .collect::<Vec<FineTuning>>(),
+                    .collect::<Result<Vec<FineTuning>, _>>()
+                    .map_err(ClientError::Deserialize)?,
             )),
+            crate::prelude::Purpose::FineTuneResults => Self::Response::FineTuneResults(text),
+            crate::prelude::Purpose::Custom(_) => Self::Response::Other(text),
         };
         Ok(file)
     }
-    async fn request_raw(&self, client: &Client) -> reqwest::Result<String> {
+    async fn request_raw(&self, client: &Client) -> Result<String, ClientError> {
         crate::api::files::content::Request {
             file_id: self.file_id.clone(),
         }
@@ -94,44 +106,46 @@ impl crate::client::Request for crate::api::files::content_checked::Request {
 impl crate::client::Request for crate::api::files::content_checked::Request {
     type Response = crate::api::files::content_checked::Response;
 
-    fn request(&self, client: &Client) -> reqwest::Result<Self::Response> {
+    fn request(&self, client: &Client) -> Result<Self::Response, ClientError> {
         let metadata =
             crate::api::files::metadata::Request::new(self.file_id.clone()).request(client)?;
 
         let response =
             crate::api::files::content::Request::new(self.file_id.clone()).request_raw(client)?;
-        let text = dbg!(response);
+        let text = response;
         let iter = text.lines();
 
         let file = match metadata.purpose {
             crate::prelude::Purpose::Search => Self::Response::Search(File::new(
                 metadata.filename,
                 iter.map(serde_json::from_str)
-                    .map(Result::unwrap)
-                    .collect::<Vec<Search>>(),
+                    .collect::<Result<Vec<Search>, _>>()
+                    .map_err(ClientError::Deserialize)?,
             )),
             crate::prelude::Purpose::Answers => Self::Response::Answers(File::new(
                 metadata.filename,
                 iter.map(serde_json::from_str)
-                    .map(Result::unwrap)
-                    .collect::<Vec<Answers>>(),
+                    .collect::<Result<Vec<Answers>, _>>()
+                    .map_err(ClientError::Deserialize)?,
             )),
             crate::prelude::Purpose::Classifications => Self::Response::Classifications(File::new(
                 metadata.filename,
                 iter.map(serde_json::from_str)
-                    .map(Result::unwrap)
-                    .collect::<Vec<Classifications>>(),
+                    .collect::<Result<Vec<Classifications>, _>>()
+                    .map_err(ClientError::Deserialize)?,
             )),
             crate::prelude::Purpose::FineTuning => Self::Response::FineTuning(File::new(
                 metadata.filename,
                 iter.map(serde_json::from_str)
-                    .map(Result::unwrap)
-                    .collect::<Vec<FineTuning>>(),
+                    .collect::<Result<Vec<FineTuning>, _>>()
+                    .map_err(ClientError::Deserialize)?,
             )),
+            crate::prelude::Purpose::FineTuneResults => Self::Response::FineTuneResults(text),
+            crate::prelude::Purpose::Custom(_) => Self::Response::Other(text),
         };
         Ok(file)
     }
-    fn request_raw(&self, client: &Client) -> reqwest::Result<String> {
+    fn request_raw(&self, client: &Client) -> Result<String, ClientError> {
         crate::api::files::content::Request {
             file_id: self.file_id.clone(),
         }
@@ -1,5 +1,4 @@
-use crate::api::BuildRequest;
-use crate::OPENAI_URL;
+use crate::api::{Auth, BuildRequest, ObjectType};
 use serde::{Deserialize, Serialize};
 
 /// # OpenAi documentation
@@ -15,12 +14,11 @@ impl Request {
     }
 }
 impl BuildRequest for Request {
-
     fn build_request(&self, client: &crate::Client) -> crate::RequestBuilder {
         client
             .reqwest_client()
-            .delete(format!("{OPENAI_URL}/files/{}", self.file_id))
-            .bearer_auth(client.gpt_token())
+            .delete(format!("{}/files/{}", client.base_url(), self.file_id))
+            .auth(client)
     }
 }
 
@@ -29,7 +27,7 @@ pub struct Response {
     /// The file id used to identify the file
     pub id: String,
     /// The object of the request
-    pub object: String,
+    pub object: ObjectType,
     /// Whether the deletion was successful or not
     pub deleted: bool,
 }
@@ -37,4 +35,4 @@ pub struct Response {
 #[cfg_attr(not(feature = "blocking"), async_trait::async_trait)]
 impl crate::client::Request for Request {
     type Response = Response;
-}
\ No newline at end of file
+}
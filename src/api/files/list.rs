@@ -1,32 +1,160 @@
 use crate::api::files;
-use crate::api::BuildRequest;
-use crate::OPENAI_URL;
+use crate::api::{self, Auth, BuildRequest, ObjectType, Page, Paginated};
+use crate::{Client, ClientError};
 use serde::{Deserialize, Serialize};
 
 /// # OpenAi documentation
 ///
 /// Returns a list of files that belong to the user's organization.
-#[derive(Debug, Clone, PartialEq)]
-pub struct Request;
+///
+/// Use [`list_all`](Request::list_all) to follow the pages of large listings.
+/// # Example
+/// ```ignore
+/// let page = files::list::Request::default().limit(10).request(&client).await?;
+/// let files = files::list::Request::default().list_all(&client).await?.data;
+/// ```
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Request {
+    /// A cursor for pagination, the id of the last file of the previous page
+    pub after: Option<String>,
+    /// The number of files to return per page
+    pub limit: Option<u64>,
+}
+impl Request {
+    /// Sets the cursor, the id of the last file of the previous page
+    pub fn after(mut self, after: impl Into<String>) -> Self {
+        self.after = Some(after.into());
+        self
+    }
+    /// Sets the number of files to return per page
+    pub fn limit(mut self, limit: u64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+    /// Requests the pages starting at [`after`](Self::after) until the last one and returns all files as one page
+    ///
+    /// Stops after 100 pages, in case the api keeps reporting more pages.
+    /// [`has_more`](Response::has_more) is then still set, to tell the listing is incomplete.
+    #[cfg(not(feature = "blocking"))]
+    pub async fn list_all(&self, client: &Client) -> Result<Response, ClientError> {
+        api::list_all(client, self).await
+    }
+    /// Requests the pages starting at [`after`](Self::after) until the last one and returns all files as one page
+    ///
+    /// Stops after 100 pages, in case the api keeps reporting more pages.
+    /// [`has_more`](Response::has_more) is then still set, to tell the listing is incomplete.
+    #[cfg(feature = "blocking")]
+    pub fn list_all(&self, client: &Client) -> Result<Response, ClientError> {
+        api::list_all(client, self)
+    }
+}
+impl Paginated for Request {
+    fn set_after(&mut self, after: String) {
+        self.after = Some(after);
+    }
+}
+impl Page for Response {
+    fn last_id(&self) -> Option<String> {
+        self.data.last().map(|file| file.id.clone())
+    }
+    fn has_more(&self) -> bool {
+        self.has_more
+    }
+    fn append(&mut self, mut next: Self) {
+        self.data.append(&mut next.data);
+        self.has_more = next.has_more;
+    }
+}
 
 impl BuildRequest for Request {
-
     fn build_request(&self, client: &crate::Client) -> crate::RequestBuilder {
-        client
+        let mut builder = client
             .reqwest_client()
-            .get(format!("{OPENAI_URL}/files"))
-            .bearer_auth(client.gpt_token())
+            .get(format!("{}/files", client.base_url()))
+            .auth(client);
+        if let Some(after) = &self.after {
+            builder = builder.query(&[("after", after)]);
+        }
+        if let Some(limit) = self.limit {
+            builder = builder.query(&[("limit", limit)]);
+        }
+        builder
     }
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Response {
     /// List of files and metadata uploaded to the storage
-    pub data: Vec<files::metadata::Response>,
+    pub data: Vec<files::FileInfo>,
     /// Action of the request
-    pub object: String,
+    pub object: ObjectType,
+    /// Whether there are more files after this page
+    #[serde(default)]
+    pub has_more: bool,
 }
 #[cfg_attr(not(feature = "blocking"), async_trait::async_trait)]
 impl crate::client::Request for Request {
     type Response = Response;
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::Request;
+    use crate::{transport::MockTransport, Client};
+
+    #[test]
+    fn list_all() {
+        let page = |ids: &[&str], has_more: bool| {
+            let files = ids
+                .iter()
+                .map(|id| {
+                    format!(
+                        r#"{{"id": "{id}", "object": "file", "bytes": 1, "created_at": 1, "filename": "{id}.jsonl", "purpose": "search"}}"#
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(",");
+            format!(r#"{{"object": "list", "data": [{files}], "has_more": {has_more}}}"#)
+        };
+        let transport = Arc::new(
+            MockTransport::new()
+                .respond(200, page(&["file-a", "file-b"], true))
+                .respond(200, page(&["file-c"], false)),
+        );
+        let client = Client::with_transport("token", transport.clone());
+
+        let files = wait!(Request::default().limit(2).list_all(&client)).unwrap();
+
+        assert!(!files.has_more);
+        let ids = files
+            .data
+            .iter()
+            .map(|file| file.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, ["file-a", "file-b", "file-c"]);
+        let queries = transport
+            .requests()
+            .iter()
+            .map(|request| request.url.query().unwrap_or_default().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(queries, ["limit=2", "after=file-b&limit=2"]);
+    }
+    #[test]
+    fn page_cap() {
+        let transport = (0..150).fold(MockTransport::new(), |transport, i| {
+            transport.respond(
+                200,
+                format!(
+                    r#"{{"object": "list", "data": [{{"id": "file-{i}", "object": "file", "bytes": 1, "created_at": 1, "filename": "f.jsonl", "purpose": "search"}}], "has_more": true}}"#
+                ),
+            )
+        });
+        let client = Client::with_transport("token", transport);
+
+        let files = wait!(Request::default().list_all(&client)).unwrap();
+        assert_eq!(files.data.len(), 100);
+        assert!(files.has_more);
+    }
+}
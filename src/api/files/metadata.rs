@@ -1,7 +1,5 @@
-use crate::api::{BuildRequest};
-use crate::prelude::Purpose;
-use crate::OPENAI_URL;
-use serde::{Deserialize, Serialize};
+use crate::api::files::FileInfo;
+use crate::api::{Auth, BuildRequest};
 
 /// # OpenAi documentation
 ///
@@ -16,30 +14,16 @@ impl Request {
     }
 }
 impl BuildRequest for Request {
-
     fn build_request(&self, client: &crate::Client) -> crate::RequestBuilder {
         client
             .reqwest_client()
-            .get(format!("{OPENAI_URL}/files/{}", self.file_id))
-            .bearer_auth(client.gpt_token())
+            .get(format!("{}/files/{}", client.base_url(), self.file_id))
+            .auth(client)
     }
 }
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub struct Response {
-    /// The file id used to identify the file
-    pub id: String,
-    /// The object of the request
-    pub object: String,
-    /// The size of the file in bytes
-    pub bytes: u64,
-    /// The time the file was uploaded
-    pub created_at: u64,
-    /// The name of the file
-    pub filename: String,
-    /// The purpose of the file
-    pub purpose: Purpose,
-}
+/// The metadata of the file
+pub type Response = FileInfo;
 #[cfg_attr(not(feature = "blocking"), async_trait::async_trait)]
 impl crate::client::Request for Request {
     type Response = Response;
-}
\ No newline at end of file
+}
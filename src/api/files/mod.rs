@@ -3,10 +3,11 @@
 //! Files are represented as a [`File<T>`] where `T` represents the file type
 //! valid types are [`Search`], [`Answers`], [`Classifications`] and [`FineTuning`]
 //!  
+use std::{collections::HashMap, path::Path};
 
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
-use super::Purpose;
+use super::{ObjectType, Purpose};
 
 pub mod content;
 pub mod content_checked;
@@ -15,6 +16,29 @@ pub mod list;
 pub mod metadata;
 pub mod upload;
 
+/// Retrieve the [`FileInfo`] of a single file, an alias for [`metadata`]
+pub use metadata as retrieve;
+
+/// Information about an uploaded file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileInfo {
+    /// The file id used to identify the file
+    pub id: String,
+    /// The object of the request
+    pub object: ObjectType,
+    /// The size of the file in bytes
+    pub bytes: u64,
+    /// The time the file was uploaded
+    pub created_at: u64,
+    /// The name of the file
+    pub filename: String,
+    /// The purpose of the file
+    pub purpose: Purpose,
+    /// Fields returned by the api that aren't modeled by this crate yet
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
 pub trait FilePurpose {
     fn purpose(&self) -> Purpose;
 }
@@ -52,6 +76,7 @@ pub struct FineTuning {
     pub prompt: String,
     pub completion: String,
 }
+/// An already serialized file, uploaded as is
 #[derive(Debug, Clone)]
 pub struct Raw {
     pub name: String,
@@ -59,6 +84,28 @@ pub struct Raw {
     pub purpose: Purpose,
 }
 
+impl Raw {
+    /// Reads a jsonl file from disk, using its file name as the name of the upload
+    /// # Example
+    /// ```ignore
+    /// let file = files::Raw::from_path("answers.jsonl", Purpose::Answers)?;
+    /// let response = files::upload::Request::new(file).request(&client).await?;
+    /// ```
+    pub fn from_path(path: impl AsRef<Path>, purpose: Purpose) -> std::io::Result<Self> {
+        let path = path.as_ref();
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let data = std::fs::read_to_string(path)?;
+        Ok(Raw {
+            name,
+            data,
+            purpose,
+        })
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct File<T> {
     #[serde(skip)]
@@ -96,7 +143,7 @@ impl FilePurpose for File<FineTuning> {
 }
 impl FilePurpose for Raw {
     fn purpose(&self) -> Purpose {
-        self.purpose
+        self.purpose.clone()
     }
 }
 impl<T> ValidFile for File<Search<T>> {}
@@ -104,3 +151,66 @@ impl<T> ValidFile for File<Answers<T>> {}
 impl<T> ValidFile for File<Classifications<T>> {}
 impl ValidFile for File<FineTuning> {}
 impl ValidFile for Raw {}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::Method;
+
+    use crate::api::BuildRequest;
+    use crate::Client;
+
+    #[test]
+    fn content_uses_get() {
+        let client = Client::new("token".to_string());
+        let request = super::content::Request::new("file-id".to_string())
+            .build_request(&client)
+            .build()
+            .unwrap();
+
+        assert_eq!(request.method(), Method::GET);
+        assert_eq!(
+            request.url().as_str(),
+            "https://api.openai.com/v1/files/file-id/content"
+        );
+    }
+    #[test]
+    fn delete_uses_delete() {
+        let client = Client::new("token".to_string());
+        let request = super::delete::Request::new("file-id".to_string())
+            .build_request(&client)
+            .build()
+            .unwrap();
+
+        assert_eq!(request.method(), Method::DELETE);
+        assert_eq!(
+            request.url().as_str(),
+            "https://api.openai.com/v1/files/file-id"
+        );
+    }
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn retrieve_missing_file() {
+        let body = r#"{
+            "error": {
+                "message": "No such File object: file-id",
+                "type": "invalid_request_error",
+                "param": "id",
+                "code": null
+            }
+        }"#;
+        let response = http::Response::builder()
+            .status(404)
+            .body(body)
+            .unwrap()
+            .into();
+
+        match crate::client::check_status(response) {
+            Err(crate::ClientError::Api(error)) => {
+                assert_eq!(error.status, 404);
+                assert_eq!(error.kind, "invalid_request_error");
+                assert_eq!(error.param.as_deref(), Some("id"));
+            }
+            other => panic!("expected an api error, got {other:?}"),
+        }
+    }
+}
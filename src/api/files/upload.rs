@@ -1,9 +1,10 @@
-use crate::api::BuildRequest;
+use crate::api::{Auth, BuildRequest};
 use crate::prelude::Purpose;
-use crate::{Form, Part, OPENAI_URL};
-use serde::{Deserialize, Serialize};
+use crate::{Form, Part};
+use serde::Serialize;
+use std::path::Path;
 
-use super::{File, FilePurpose, Raw, ValidFile};
+use super::{File, FileInfo, FilePurpose, Raw, ValidFile};
 
 /// # OpenAi documentation
 ///
@@ -20,6 +21,14 @@ impl<T> Request<T> {
         Request { file }
     }
 }
+impl Request<Raw> {
+    /// Creates a request uploading a jsonl file from disk
+    ///
+    /// See [`Raw::from_path`]
+    pub fn from_path(path: impl AsRef<Path>, purpose: Purpose) -> std::io::Result<Self> {
+        Ok(Request::new(Raw::from_path(path, purpose)?))
+    }
+}
 impl<T> BuildRequest for Request<File<T>>
 where
     File<T>: FilePurpose + ValidFile,
@@ -44,8 +53,8 @@ where
 
         client
             .reqwest_client()
-            .post(format!("{OPENAI_URL}/files"))
-            .bearer_auth(client.gpt_token())
+            .post(format!("{}/files", client.base_url()))
+            .auth(client)
             .multipart(form)
     }
 }
@@ -62,26 +71,13 @@ impl BuildRequest for Request<Raw> {
 
         client
             .reqwest_client()
-            .post(format!("{OPENAI_URL}/files"))
-            .bearer_auth(client.gpt_token())
+            .post(format!("{}/files", client.base_url()))
+            .auth(client)
             .multipart(form)
     }
 }
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub struct Response {
-    /// The file id used to identify the file
-    pub id: String,
-    /// The object of the request
-    pub object: String,
-    /// The size of the file in bytes
-    pub bytes: u64,
-    /// The time the file was uploaded
-    pub created_at: u64,
-    /// The name of the file
-    pub filename: String,
-    /// The purpose of the file
-    pub purpose: Purpose,
-}
+/// The uploaded file
+pub type Response = FileInfo;
 #[cfg_attr(not(feature = "blocking"), async_trait::async_trait)]
 impl<T> crate::client::Request for Request<T>
 where
@@ -90,3 +86,22 @@ where
 {
     type Response = Response;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Request;
+    use crate::api::Purpose;
+
+    #[test]
+    fn from_path() {
+        let path = std::env::temp_dir().join("gpt3_rs_upload_from_path.jsonl");
+        std::fs::write(&path, "{\"text\": \"say hi\"}\n").unwrap();
+
+        let request = Request::from_path(&path, Purpose::Search).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(request.file.name, "gpt3_rs_upload_from_path.jsonl");
+        assert_eq!(request.file.data, "{\"text\": \"say hi\"}\n");
+        assert_eq!(request.file.purpose, Purpose::Search);
+    }
+}
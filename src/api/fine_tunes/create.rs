@@ -0,0 +1,86 @@
+//! Create a fine-tune job
+//! # Builder
+//! Use the [`fine_tunes::create::Builder`][struct@Builder] to construct a [`fine_tunes::create::Request`][Request] struct
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use crate::api::RequestInfo;
+use crate::model::Model;
+
+use super::FineTune;
+/// Create a fine-tune job
+///
+/// # OpenAi documentation
+/// Creates a job that fine-tunes a specified model from a given dataset.
+///
+/// Response includes details of the enqueued job including job status and the name of the fine-tuned models once complete.
+/// # Example
+/// ```ignore
+/// let request = fine_tunes::create::Builder::default()
+///     .training_file("file-XGinujblHPwGLSztz8cPS8XY")
+///     .model(Model::Curie)
+///     .suffix("custom-model-name")
+///     .build()
+///     .unwrap();
+/// ```
+/// # Required
+/// ```ignore
+/// training_file
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Builder)]
+#[builder_struct_attr(doc = "# Required")]
+#[builder_struct_attr(doc = "[`training_file`][Self::training_file()]")]
+#[builder_struct_attr(doc = "")]
+#[builder(name = "Builder")]
+pub struct Request {
+    /// The ID of an uploaded file that contains training data.
+    /// Your dataset must be formatted as a JSONL file, where each training example is a JSON object with the keys "prompt" and "completion".
+    /// Additionally, you must upload your file with the purpose fine-tune.
+    #[builder(setter(into))]
+    pub training_file: String,
+    /// The ID of an uploaded file that contains validation data.
+    /// If you provide this file, the data is used to generate validation metrics periodically during fine-tuning.
+    /// Your train and validation data should be mutually exclusive.
+    #[builder(default, setter(strip_option, into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validation_file: Option<String>,
+    /// The name of the base model to fine-tune. You can select one of "ada", "babbage", "curie", "davinci", or a fine-tuned model.
+    /// # Default
+    /// Defaults to curie
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<Model>,
+    /// The number of epochs to train the model for. An epoch refers to one full cycle through the training dataset.
+    /// # Default
+    /// Defaults to 4
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n_epochs: Option<u64>,
+    /// The batch size to use for training. The batch size is the number of training examples used to train a single forward and backward pass.
+    /// By default, the batch size will be dynamically configured to be ~0.2% of the number of examples in the training set, capped at 256.
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub batch_size: Option<u64>,
+    /// The learning rate multiplier to use for training. The fine-tuning learning rate is the original learning rate used for pretraining multiplied by this value.
+    /// By default, the learning rate multiplier is the 0.05, 0.1, or 0.2 depending on final batch_size.
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub learning_rate_multiplier: Option<f64>,
+    /// A string of up to 40 characters that will be added to your fine-tuned model name.
+    /// For example, a suffix of "custom-model-name" would produce a model name like `ada:ft-your-org:custom-model-name-2022-02-15-04-21-04`.
+    #[builder(default, setter(strip_option, into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suffix: Option<String>,
+}
+/// A response corresponding to a [`Request`]
+pub type Response = FineTune;
+
+impl RequestInfo for Request {
+    fn url(&self, base_url: &str) -> String {
+        format!("{base_url}/fine-tunes")
+    }
+}
+#[cfg_attr(not(feature = "blocking"), async_trait::async_trait)]
+impl crate::client::Request for Request {
+    type Response = Response;
+}
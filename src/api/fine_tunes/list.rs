@@ -0,0 +1,140 @@
+use crate::api::{self, Auth, BuildRequest, ObjectType, Page, Paginated};
+use crate::{Client, ClientError};
+use serde::{Deserialize, Serialize};
+
+use super::FineTune;
+
+/// # OpenAi documentation
+///
+/// List your organization's fine-tuning jobs
+///
+/// Use [`list_all`](Request::list_all) to follow the pages of large listings.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Request {
+    /// A cursor for pagination, the id of the last fine-tune job of the previous page
+    pub after: Option<String>,
+    /// The number of fine-tune jobs to return per page
+    pub limit: Option<u64>,
+}
+impl Request {
+    /// Sets the cursor, the id of the last fine-tune job of the previous page
+    pub fn after(mut self, after: impl Into<String>) -> Self {
+        self.after = Some(after.into());
+        self
+    }
+    /// Sets the number of fine-tune jobs to return per page
+    pub fn limit(mut self, limit: u64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+    /// Requests the pages starting at [`after`](Self::after) until the last one and returns all fine-tune jobs as one page
+    ///
+    /// Stops after 100 pages, in case the api keeps reporting more pages.
+    /// [`has_more`](Response::has_more) is then still set, to tell the listing is incomplete.
+    #[cfg(not(feature = "blocking"))]
+    pub async fn list_all(&self, client: &Client) -> Result<Response, ClientError> {
+        api::list_all(client, self).await
+    }
+    /// Requests the pages starting at [`after`](Self::after) until the last one and returns all fine-tune jobs as one page
+    ///
+    /// Stops after 100 pages, in case the api keeps reporting more pages.
+    /// [`has_more`](Response::has_more) is then still set, to tell the listing is incomplete.
+    #[cfg(feature = "blocking")]
+    pub fn list_all(&self, client: &Client) -> Result<Response, ClientError> {
+        api::list_all(client, self)
+    }
+}
+impl Paginated for Request {
+    fn set_after(&mut self, after: String) {
+        self.after = Some(after);
+    }
+}
+impl Page for Response {
+    fn last_id(&self) -> Option<String> {
+        self.data.last().map(|fine_tune| fine_tune.id.clone())
+    }
+    fn has_more(&self) -> bool {
+        self.has_more
+    }
+    fn append(&mut self, mut next: Self) {
+        self.data.append(&mut next.data);
+        self.has_more = next.has_more;
+    }
+}
+
+impl BuildRequest for Request {
+    fn build_request(&self, client: &crate::Client) -> crate::RequestBuilder {
+        let mut builder = client
+            .reqwest_client()
+            .get(format!("{}/fine-tunes", client.base_url()))
+            .auth(client);
+        if let Some(after) = &self.after {
+            builder = builder.query(&[("after", after)]);
+        }
+        if let Some(limit) = self.limit {
+            builder = builder.query(&[("limit", limit)]);
+        }
+        builder
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Response {
+    /// List of fine-tune jobs, without their events
+    pub data: Vec<FineTune>,
+    /// Action of the request
+    pub object: ObjectType,
+    /// Whether there are more fine-tune jobs after this page
+    #[serde(default)]
+    pub has_more: bool,
+}
+#[cfg_attr(not(feature = "blocking"), async_trait::async_trait)]
+impl crate::client::Request for Request {
+    type Response = Response;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::Request;
+    use crate::{transport::MockTransport, Client};
+
+    #[test]
+    fn list_all() {
+        let page = |ids: &[&str], has_more: bool| {
+            let fine_tunes = ids
+                .iter()
+                .map(|id| {
+                    format!(
+                        r#"{{"id": "{id}", "object": "fine-tune", "model": "curie", "created_at": 1, "fine_tuned_model": null, "hyperparams": {{"n_epochs": 4, "prompt_loss_weight": 0.01}}, "organization_id": "org-a", "result_files": [], "status": "pending", "validation_files": [], "training_files": [], "updated_at": 1}}"#
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(",");
+            format!(r#"{{"object": "list", "data": [{fine_tunes}], "has_more": {has_more}}}"#)
+        };
+        let transport = Arc::new(
+            MockTransport::new()
+                .respond(200, page(&["ft-a", "ft-b"], true))
+                .respond(200, page(&["ft-c"], false)),
+        );
+        let client = Client::with_transport("token", transport.clone());
+
+        let fine_tunes = wait!(Request::default().limit(2).list_all(&client)).unwrap();
+
+        assert!(!fine_tunes.has_more);
+        let ids = fine_tunes
+            .data
+            .iter()
+            .map(|fine_tune| fine_tune.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, ["ft-a", "ft-b", "ft-c"]);
+        let queries = transport
+            .requests()
+            .iter()
+            .map(|request| request.url.query().unwrap_or_default().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(queries, ["limit=2", "after=ft-b&limit=2"]);
+    }
+}
@@ -0,0 +1,144 @@
+//! This module contains other modules that are used to interact with the fine-tunes api
+//!
+//! A fine-tune job is represented as a [`FineTune`], every request of this module responds with one.
+//!  
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{files::FileInfo, ObjectType};
+
+pub mod cancel;
+pub mod create;
+pub mod list;
+pub mod retrieve;
+
+/// A fine-tune job
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FineTune {
+    /// The fine-tune id used to identify the job
+    pub id: String,
+    /// The object of the request
+    pub object: ObjectType,
+    /// The base model that is being fine-tuned
+    pub model: String,
+    /// The time the job was created
+    pub created_at: u64,
+    /// The events of the job, not included when listing jobs
+    #[serde(default)]
+    pub events: Vec<Event>,
+    /// The name of the fine-tuned model, only set once the job succeeded
+    pub fine_tuned_model: Option<String>,
+    /// The hyperparameters used for training
+    pub hyperparams: Hyperparams,
+    /// The organization that owns the job
+    pub organization_id: String,
+    /// The files containing the results of the training
+    pub result_files: Vec<FileInfo>,
+    /// The status of the job, e.g. `pending`, `running`, `succeeded`, `failed` or `cancelled`
+    pub status: String,
+    /// The files used for validation
+    pub validation_files: Vec<FileInfo>,
+    /// The files used for training
+    pub training_files: Vec<FileInfo>,
+    /// The last time the job was updated
+    pub updated_at: u64,
+    /// Fields returned by the api that aren't modeled by this crate yet
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// A status update of a fine-tune job
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Event {
+    /// The object of the event
+    pub object: ObjectType,
+    /// The time the event happened
+    pub created_at: u64,
+    /// The level of the event, e.g. `info`
+    pub level: String,
+    /// A description of the event
+    pub message: String,
+}
+
+/// The hyperparameters of a fine-tune job
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Hyperparams {
+    /// The batch size used for training
+    pub batch_size: Option<u64>,
+    /// The learning rate multiplier used for training
+    pub learning_rate_multiplier: Option<f64>,
+    /// The number of epochs trained for
+    pub n_epochs: u64,
+    /// The weight of the prompt loss
+    pub prompt_loss_weight: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::Method;
+
+    use crate::api::BuildRequest;
+    use crate::Client;
+
+    #[test]
+    fn cancel_uses_post() {
+        let client = Client::new("token".to_string());
+        let request = super::cancel::Request::new("ft-id".to_string())
+            .build_request(&client)
+            .build()
+            .unwrap();
+
+        assert_eq!(request.method(), Method::POST);
+        assert_eq!(
+            request.url().as_str(),
+            "https://api.openai.com/v1/fine-tunes/ft-id/cancel"
+        );
+    }
+    #[test]
+    fn deserialize_job() {
+        let json = r#"{
+            "id": "ft-AF1WoRqd3aJAHsqc9NY7iL8F",
+            "object": "fine-tune",
+            "model": "curie",
+            "created_at": 1614807352,
+            "events": [
+                {
+                    "object": "fine-tune-event",
+                    "created_at": 1614807352,
+                    "level": "info",
+                    "message": "Job enqueued. Waiting for jobs ahead to complete. Queue number: 0."
+                }
+            ],
+            "fine_tuned_model": null,
+            "hyperparams": {
+                "batch_size": 4,
+                "learning_rate_multiplier": 0.1,
+                "n_epochs": 4,
+                "prompt_loss_weight": 0.1
+            },
+            "organization_id": "org-...",
+            "result_files": [],
+            "status": "pending",
+            "validation_files": [],
+            "training_files": [
+                {
+                    "id": "file-XGinujblHPwGLSztz8cPS8XY",
+                    "object": "file",
+                    "bytes": 1547276,
+                    "created_at": 1610062281,
+                    "filename": "my-data-train.jsonl",
+                    "purpose": "fine-tune"
+                }
+            ],
+            "updated_at": 1614807352
+        }"#;
+
+        let job: super::FineTune = serde_json::from_str(json).unwrap();
+
+        assert_eq!(job.status, "pending");
+        assert_eq!(job.fine_tuned_model, None);
+        assert_eq!(job.events.len(), 1);
+        assert_eq!(job.training_files[0].filename, "my-data-train.jsonl");
+    }
+}
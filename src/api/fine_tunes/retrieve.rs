@@ -0,0 +1,35 @@
+use crate::api::{Auth, BuildRequest};
+
+use super::FineTune;
+
+/// # OpenAi documentation
+///
+/// Gets info about the fine-tune job.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request {
+    pub fine_tune_id: String,
+}
+impl Request {
+    pub fn new(fine_tune_id: String) -> Self {
+        Request { fine_tune_id }
+    }
+}
+impl BuildRequest for Request {
+    fn build_request(&self, client: &crate::Client) -> crate::RequestBuilder {
+        client
+            .reqwest_client()
+            .get(format!(
+                "{}/fine-tunes/{}",
+                client.base_url(),
+                self.fine_tune_id
+            ))
+            .auth(client)
+    }
+}
+/// The fine-tune job, including its events
+pub type Response = FineTune;
+
+#[cfg_attr(not(feature = "blocking"), async_trait::async_trait)]
+impl crate::client::Request for Request {
+    type Response = Response;
+}
@@ -0,0 +1,128 @@
+//! Edit an image from a prompt
+//! # Builder
+//! Use the [`images::edits::Builder`][struct@Builder] to construct an [`images::edits::Request`][Request] struct
+use derive_builder::Builder;
+
+use crate::api::{check_range, user_hash, Auth, BuildRequest};
+use crate::Form;
+
+use super::{with_options, Image, ImageFormat, ImageSize};
+/// Edit an image from a prompt
+///
+/// # OpenAi documentation
+/// Creates an edited or extended image given an original image and a prompt.
+/// # Example
+/// ```ignore
+/// let request = images::edits::Builder::default()
+///     .image(Image::from_path("otter.png")?)
+///     .mask(Image::from_path("mask.png")?)
+///     .prompt("A cute baby sea otter wearing a beret")
+///     .build()
+///     .unwrap();
+/// ```
+/// # Required
+/// ```ignore
+/// image, prompt
+/// ```
+#[derive(Debug, Clone, PartialEq, Builder)]
+#[builder_struct_attr(doc = "# Required")]
+#[builder_struct_attr(doc = "[`image`][Self::image()]")]
+#[builder_struct_attr(doc = "[`prompt`][Self::prompt()]")]
+#[builder_struct_attr(doc = "")]
+#[builder(name = "Builder", build_fn(validate = "Self::validate"))]
+pub struct Request {
+    /// The image to edit. Must be a valid PNG file, less than 4MB, and square.
+    /// If mask is not provided, image must have transparency, which will be used as the mask.
+    pub image: Image,
+    /// An additional image whose fully transparent areas (e.g. where alpha is zero) indicate where image should be edited.
+    /// Must be a valid PNG file, less than 4MB, and have the same dimensions as image.
+    #[builder(default, setter(strip_option))]
+    pub mask: Option<Image>,
+    /// A text description of the desired image(s). The maximum length is 1000 characters.
+    #[builder(setter(into))]
+    pub prompt: String,
+    /// The number of images to generate. Must be between 1 and 10.
+    /// # Default
+    /// Defaults to 1
+    #[builder(default, setter(strip_option))]
+    pub n: Option<u8>,
+    /// The size of the generated images.
+    /// # Default
+    /// Defaults to [`ImageSize::Size1024`]
+    #[builder(default, setter(strip_option))]
+    pub size: Option<ImageSize>,
+    /// The format in which the generated images are returned.
+    /// # Default
+    /// Defaults to [`ImageFormat::Url`]
+    #[builder(default, setter(strip_option))]
+    pub response_format: Option<ImageFormat>,
+    /// A unique identifier representing your end-user, which will help OpenAI to monitor and detect abuse.
+    /// It should not contain identifiable information, see [`Builder::user_hash`].
+    #[builder(default, setter(strip_option, into))]
+    pub user: Option<String>,
+}
+/// A response corresponding to a [`Request`]
+pub type Response = super::Response;
+
+impl Builder {
+    user_hash!();
+    fn validate(&self) -> Result<(), String> {
+        check_range("n", self.n, 1, 10)?;
+        Ok(())
+    }
+}
+impl BuildRequest for Request {
+    fn build_request(&self, client: &crate::Client) -> crate::RequestBuilder {
+        let mut form = Form::new()
+            .part("image", self.image.part())
+            .text("prompt", self.prompt.clone());
+        if let Some(mask) = &self.mask {
+            form = form.part("mask", mask.part());
+        }
+        let form = with_options(form, self.n, self.size, self.response_format, &self.user);
+
+        client
+            .reqwest_client()
+            .post(format!("{}/images/edits", client.base_url()))
+            .auth(client)
+            .multipart(form)
+    }
+}
+#[cfg_attr(not(feature = "blocking"), async_trait::async_trait)]
+impl crate::client::Request for Request {
+    type Response = Response;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Builder;
+    use crate::api::{images::Image, BuildRequest};
+    use crate::Client;
+
+    #[test]
+    fn multipart_request() {
+        let request = Builder::default()
+            .image(Image::new(
+                "otter.png".to_string(),
+                vec![0x89, b'P', b'N', b'G'],
+            ))
+            .prompt("A cute baby sea otter wearing a beret")
+            .n(2)
+            .build()
+            .unwrap();
+
+        let built = request
+            .build_request(&Client::new("token".to_string()))
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            built.url().as_str(),
+            "https://api.openai.com/v1/images/edits"
+        );
+        assert!(built.headers()["content-type"]
+            .to_str()
+            .unwrap()
+            .starts_with("multipart/form-data"));
+    }
+}
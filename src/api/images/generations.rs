@@ -0,0 +1,115 @@
+//! Create images from a prompt
+//! # Builder
+//! Use the [`images::generations::Builder`][struct@Builder] to construct an [`images::generations::Request`][Request] struct
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use crate::api::{check_range, user_hash, RequestInfo};
+
+use super::{ImageFormat, ImageSize};
+/// Create images from a prompt
+///
+/// # OpenAi documentation
+/// Creates an image given a prompt.
+/// # Example
+/// ```ignore
+/// let request = images::generations::Builder::default()
+///     .prompt("A cute baby sea otter")
+///     .n(2)
+///     .size(ImageSize::Size1024)
+///     .build()
+///     .unwrap();
+/// ```
+/// # Required
+/// ```ignore
+/// prompt
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Builder)]
+#[builder_struct_attr(doc = "# Required")]
+#[builder_struct_attr(doc = "[`prompt`][Self::prompt()]")]
+#[builder_struct_attr(doc = "")]
+#[builder(name = "Builder", build_fn(validate = "Self::validate"))]
+pub struct Request {
+    /// A text description of the desired image(s). The maximum length is 1000 characters.
+    #[builder(setter(into))]
+    pub prompt: String,
+    /// The number of images to generate. Must be between 1 and 10.
+    /// # Default
+    /// Defaults to 1
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n: Option<u8>,
+    /// The size of the generated images.
+    /// # Default
+    /// Defaults to [`ImageSize::Size1024`]
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<ImageSize>,
+    /// The format in which the generated images are returned.
+    /// # Default
+    /// Defaults to [`ImageFormat::Url`]
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<ImageFormat>,
+    /// A unique identifier representing your end-user, which will help OpenAI to monitor and detect abuse.
+    /// It should not contain identifiable information, see [`Builder::user_hash`].
+    #[builder(default, setter(strip_option, into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+}
+/// A response corresponding to a [`Request`]
+pub type Response = super::Response;
+
+impl Builder {
+    user_hash!();
+    fn validate(&self) -> Result<(), String> {
+        check_range("n", self.n, 1, 10)?;
+        Ok(())
+    }
+}
+impl RequestInfo for Request {
+    fn url(&self, base_url: &str) -> String {
+        format!("{base_url}/images/generations")
+    }
+}
+#[cfg_attr(not(feature = "blocking"), async_trait::async_trait)]
+impl crate::client::Request for Request {
+    type Response = Response;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Builder;
+    use crate::api::{
+        images::{ImageFormat, ImageSize},
+        RequestInfo,
+    };
+    use crate::OPENAI_URL;
+
+    #[test]
+    fn serialize() {
+        let request = Builder::default()
+            .prompt("A cute baby sea otter")
+            .n(2)
+            .size(ImageSize::Size512)
+            .response_format(ImageFormat::B64Json)
+            .build()
+            .unwrap();
+
+        let json = serde_json::to_value(&request).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "prompt": "A cute baby sea otter",
+                "n": 2,
+                "size": "512x512",
+                "response_format": "b64_json"
+            })
+        );
+        assert_eq!(
+            request.url(OPENAI_URL),
+            "https://api.openai.com/v1/images/generations"
+        );
+    }
+}
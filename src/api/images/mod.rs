@@ -0,0 +1,165 @@
+//! This module contains other modules that are used to interact with the images api
+//!
+//! Every request of this module responds with a [`Response`] containing the created images.
+//!  
+use std::{collections::HashMap, fmt, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Form, Part};
+
+pub mod edits;
+pub mod generations;
+pub mod variations;
+
+/// The size of the generated images
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImageSize {
+    /// 256x256 pixels
+    #[serde(rename = "256x256")]
+    Size256,
+    /// 512x512 pixels
+    #[serde(rename = "512x512")]
+    Size512,
+    /// 1024x1024 pixels
+    #[default]
+    #[serde(rename = "1024x1024")]
+    Size1024,
+}
+/// The format in which the generated images are returned
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageFormat {
+    /// A url to the image, which expires after an hour
+    #[default]
+    Url,
+    /// The image as base64 encoded json
+    B64Json,
+}
+
+impl fmt::Display for ImageSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ImageSize::Size256 => "256x256",
+            ImageSize::Size512 => "512x512",
+            ImageSize::Size1024 => "1024x1024",
+        })
+    }
+}
+impl fmt::Display for ImageFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ImageFormat::Url => "url",
+            ImageFormat::B64Json => "b64_json",
+        })
+    }
+}
+
+/// A png image to upload for edits and variations, must be square and less than 4MB
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    /// The file name of the image
+    pub name: String,
+    /// The content of the png file
+    pub data: Vec<u8>,
+}
+impl Image {
+    pub fn new(name: String, data: Vec<u8>) -> Self {
+        Image { name, data }
+    }
+    /// Reads a png image from disk, using its file name as the name of the upload
+    /// # Example
+    /// ```ignore
+    /// let image = images::Image::from_path("otter.png")?;
+    /// ```
+    pub fn from_path(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let path = path.as_ref();
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let data = std::fs::read(path)?;
+        Ok(Image { name, data })
+    }
+    fn part(&self) -> Part {
+        Part::bytes(self.data.clone()).file_name(self.name.clone())
+    }
+}
+
+/// Adds the options shared by edits and variations to a multipart form
+fn with_options(
+    mut form: Form,
+    n: Option<u8>,
+    size: Option<ImageSize>,
+    response_format: Option<ImageFormat>,
+    user: &Option<String>,
+) -> Form {
+    if let Some(n) = n {
+        form = form.text("n", n.to_string());
+    }
+    if let Some(size) = size {
+        form = form.text("size", size.to_string());
+    }
+    if let Some(response_format) = response_format {
+        form = form.text("response_format", response_format.to_string());
+    }
+    if let Some(user) = user {
+        form = form.text("user", user.clone());
+    }
+    form
+}
+
+/// The images created by a request
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Response {
+    /// The creation time of the images
+    pub created: u64,
+    /// The created images
+    pub data: Vec<ImageData>,
+    /// Fields returned by the api that aren't modeled by this crate yet
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+/// A created image, either as a url or as base64 depending on the requested [`ImageFormat`]
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImageData {
+    /// The url of the image, if the format is [`ImageFormat::Url`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// The base64 encoded image, if the format is [`ImageFormat::B64Json`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub b64_json: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ImageData, Response};
+
+    #[test]
+    fn deserialize_response() {
+        let json = r#"{
+            "created": 1589478378,
+            "data": [
+                {"url": "https://example.com/a.png"},
+                {"b64_json": "iVBORw0KGgo="}
+            ]
+        }"#;
+
+        let response: Response = serde_json::from_str(json).unwrap();
+
+        assert_eq!(response.created, 1589478378);
+        assert_eq!(
+            response.data,
+            [
+                ImageData {
+                    url: Some("https://example.com/a.png".to_string()),
+                    b64_json: None
+                },
+                ImageData {
+                    url: None,
+                    b64_json: Some("iVBORw0KGgo=".to_string())
+                },
+            ]
+        );
+    }
+}
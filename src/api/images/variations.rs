@@ -0,0 +1,79 @@
+//! Create variations of an image
+//! # Builder
+//! Use the [`images::variations::Builder`][struct@Builder] to construct an [`images::variations::Request`][Request] struct
+use derive_builder::Builder;
+
+use crate::api::{check_range, user_hash, Auth, BuildRequest};
+use crate::Form;
+
+use super::{with_options, Image, ImageFormat, ImageSize};
+/// Create variations of an image
+///
+/// # OpenAi documentation
+/// Creates a variation of a given image.
+/// # Example
+/// ```ignore
+/// let request = images::variations::Builder::default()
+///     .image(Image::from_path("otter.png")?)
+///     .n(2)
+///     .build()
+///     .unwrap();
+/// ```
+/// # Required
+/// ```ignore
+/// image
+/// ```
+#[derive(Debug, Clone, PartialEq, Builder)]
+#[builder_struct_attr(doc = "# Required")]
+#[builder_struct_attr(doc = "[`image`][Self::image()]")]
+#[builder_struct_attr(doc = "")]
+#[builder(name = "Builder", build_fn(validate = "Self::validate"))]
+pub struct Request {
+    /// The image to use as the basis for the variation(s). Must be a valid PNG file, less than 4MB, and square.
+    pub image: Image,
+    /// The number of images to generate. Must be between 1 and 10.
+    /// # Default
+    /// Defaults to 1
+    #[builder(default, setter(strip_option))]
+    pub n: Option<u8>,
+    /// The size of the generated images.
+    /// # Default
+    /// Defaults to [`ImageSize::Size1024`]
+    #[builder(default, setter(strip_option))]
+    pub size: Option<ImageSize>,
+    /// The format in which the generated images are returned.
+    /// # Default
+    /// Defaults to [`ImageFormat::Url`]
+    #[builder(default, setter(strip_option))]
+    pub response_format: Option<ImageFormat>,
+    /// A unique identifier representing your end-user, which will help OpenAI to monitor and detect abuse.
+    /// It should not contain identifiable information, see [`Builder::user_hash`].
+    #[builder(default, setter(strip_option, into))]
+    pub user: Option<String>,
+}
+/// A response corresponding to a [`Request`]
+pub type Response = super::Response;
+
+impl Builder {
+    user_hash!();
+    fn validate(&self) -> Result<(), String> {
+        check_range("n", self.n, 1, 10)?;
+        Ok(())
+    }
+}
+impl BuildRequest for Request {
+    fn build_request(&self, client: &crate::Client) -> crate::RequestBuilder {
+        let form = Form::new().part("image", self.image.part());
+        let form = with_options(form, self.n, self.size, self.response_format, &self.user);
+
+        client
+            .reqwest_client()
+            .post(format!("{}/images/variations", client.base_url()))
+            .auth(client)
+            .multipart(form)
+    }
+}
+#[cfg_attr(not(feature = "blocking"), async_trait::async_trait)]
+impl crate::client::Request for Request {
+    type Response = Response;
+}
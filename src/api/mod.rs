@@ -2,46 +2,402 @@
 //!
 //! Each of the modules contains a `module::Request` struct, usually crated with the `module::Builder`
 //! and a `module::Response` struct.
-//!  
+//!
+//! # Sending requests
+//! Every `module::Request` implements [`client::Request`](crate::Request),
+//! so all of them are sent the same way, with [`Request::request`](crate::Request::request)
+//! or [`Client::request`](crate::Client::request), regardless of the endpoint.
+//!
+//! Internally there are two ways a request describes itself:
+//! - Most requests implement [`RequestInfo`], they are sent as a json `POST` to their url.
+//! - Requests that need a different method or body (e.g. [`files`] and [`fine_tunes`])
+//!   build their http request themselves.
 
 use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
 use crate::client::Client;
+use crate::ClientError;
 pub mod answers;
+pub mod audio;
+pub mod chat;
 pub mod classifications;
 pub mod completions;
 pub mod edits;
+pub mod embeddings;
+pub mod engines;
 pub mod files;
+pub mod fine_tunes;
+pub mod images;
+pub mod moderations;
 pub mod searches;
 
+/// The log probabilities of the tokens of a completion
+///
+/// All fields have one entry per token.
+/// The entries of the first token are `None` when the prompt is echoed, because it has no preceding context.
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct LogProbs {
+    /// The tokens, as text
     pub tokens: Vec<String>,
+    /// The log probability of each token
+    #[serde(deserialize_with = "logprob::token_logprobs")]
     pub token_logprobs: Vec<Option<f64>>,
+    /// The most likely tokens at each position with their log probabilities
+    #[serde(deserialize_with = "logprob::top_logprobs")]
     pub top_logprobs: Vec<Option<HashMap<String, f64>>>,
-    pub text_offset: Vec<u64>,
+    /// The character offset of each token in the text
+    pub text_offset: Vec<usize>,
+}
+impl LogProbs {
+    /// The sampled tokens with their log probability, in the same order as [`tokens`](Self::tokens)
+    ///
+    /// The log probability is `None` for tokens without one, like the first token of an echoed prompt.
+    pub fn most_likely(&self) -> Vec<(String, Option<f64>)> {
+        self.tokens
+            .iter()
+            .zip(&self.token_logprobs)
+            .map(|(token, logprob)| (token.clone(), *logprob))
+            .collect()
+    }
+    /// The most likely tokens with their log probabilities at the position of the `index`th token
+    pub fn top_at(&self, index: usize) -> Option<&HashMap<String, f64>> {
+        self.top_logprobs.get(index)?.as_ref()
+    }
+}
+
+/// Deserializers for log probabilities, which the api sometimes sends as integers, e.g. `0`
+mod logprob {
+    use std::{collections::HashMap, fmt};
+
+    use serde::{de, Deserialize, Deserializer};
+
+    /// A log probability, accepting integer and float numbers
+    struct LogProb(f64);
+    impl<'de> Deserialize<'de> for LogProb {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct Visitor;
+            impl de::Visitor<'_> for Visitor {
+                type Value = LogProb;
+                fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    f.write_str("a number")
+                }
+                fn visit_f64<E: de::Error>(self, value: f64) -> Result<LogProb, E> {
+                    Ok(LogProb(value))
+                }
+                fn visit_i64<E: de::Error>(self, value: i64) -> Result<LogProb, E> {
+                    Ok(LogProb(value as f64))
+                }
+                fn visit_u64<E: de::Error>(self, value: u64) -> Result<LogProb, E> {
+                    Ok(LogProb(value as f64))
+                }
+            }
+            deserializer.deserialize_any(Visitor)
+        }
+    }
+
+    pub(super) fn token_logprobs<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Option<f64>>, D::Error> {
+        let logprobs = Vec::<Option<LogProb>>::deserialize(deserializer)?;
+        Ok(logprobs
+            .into_iter()
+            .map(|logprob| logprob.map(|LogProb(logprob)| logprob))
+            .collect())
+    }
+    pub(super) fn top_logprobs<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Option<HashMap<String, f64>>>, D::Error> {
+        let top = Vec::<Option<HashMap<String, LogProb>>>::deserialize(deserializer)?;
+        Ok(top
+            .into_iter()
+            .map(|top| {
+                top.map(|top| {
+                    top.into_iter()
+                        .map(|(token, LogProb(logprob))| (token, logprob))
+                        .collect()
+                })
+            })
+            .collect())
+    }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
+/// An object that is only returned as its id, unless its name is added to the `expand` parameter of the request
+///
+/// # Example
+/// ```ignore
+/// let request = classifications::Builder::default()
+///     ...
+///     .expand(&["completion"])
+///     .build()?;
+///
+/// match request.request(&client).await?.completion {
+///     Expandable::Expanded(completion) => println!("{}", completion.choices[0].text),
+///     Expandable::Id(id) => println!("{id}"),
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Expandable<T> {
+    /// The id of the object
+    Id(String),
+    /// The full object
+    Expanded(T),
+}
+impl<T> Expandable<T> {
+    /// The full object, `None` if only the id was returned
+    pub fn expanded(&self) -> Option<&T> {
+        match self {
+            Expandable::Id(_) => None,
+            Expandable::Expanded(object) => Some(object),
+        }
+    }
+}
+impl<T> Default for Expandable<T> {
+    fn default() -> Self {
+        Expandable::Id(String::new())
+    }
+}
+
+/// The amount of tokens consumed by a request
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Usage {
+    /// Tokens used by the prompt / input
+    pub prompt_tokens: u64,
+    /// Tokens generated by the model, absent for endpoints that don't generate text
+    #[serde(default)]
+    pub completion_tokens: u64,
+    /// Total tokens used by the request
+    pub total_tokens: u64,
+}
+
+/// The kind of object a response contains, sent by the api as the `object` field
+///
+/// Serialized as the id used by the api, e.g. `text_completion`.
+/// # Example
+/// ```
+/// # use gpt3_rs::api::ObjectType;
+/// let object = ObjectType::from_id("text_completion");
+/// assert_eq!(object, ObjectType::TextCompletion);
+/// assert_eq!(object, "text_completion");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ObjectType {
+    TextCompletion,
+    ChatCompletion,
+    ChatCompletionChunk,
+    Edit,
+    Embedding,
+    Classification,
+    Answer,
+    SearchResult,
+    Engine,
+    File,
+    FineTune,
+    FineTuneEvent,
+    List,
+    /// Any other object, identified by its id, for objects added to the api after this crate
+    Custom(String),
+}
+impl ObjectType {
+    /// Creates an object type from its id, falling back to [`ObjectType::Custom`] for unknown ids
+    pub fn from_id(id: &str) -> Self {
+        match id {
+            "text_completion" => ObjectType::TextCompletion,
+            "chat.completion" => ObjectType::ChatCompletion,
+            "chat.completion.chunk" => ObjectType::ChatCompletionChunk,
+            "edit" => ObjectType::Edit,
+            "embedding" => ObjectType::Embedding,
+            "classification" => ObjectType::Classification,
+            "answer" => ObjectType::Answer,
+            "search_result" => ObjectType::SearchResult,
+            "engine" => ObjectType::Engine,
+            "file" => ObjectType::File,
+            "fine-tune" => ObjectType::FineTune,
+            "fine-tune-event" => ObjectType::FineTuneEvent,
+            "list" => ObjectType::List,
+            id => ObjectType::Custom(id.to_string()),
+        }
+    }
+    /// The id of the object type as it is sent by the api
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            ObjectType::TextCompletion => "text_completion",
+            ObjectType::ChatCompletion => "chat.completion",
+            ObjectType::ChatCompletionChunk => "chat.completion.chunk",
+            ObjectType::Edit => "edit",
+            ObjectType::Embedding => "embedding",
+            ObjectType::Classification => "classification",
+            ObjectType::Answer => "answer",
+            ObjectType::SearchResult => "search_result",
+            ObjectType::Engine => "engine",
+            ObjectType::File => "file",
+            ObjectType::FineTune => "fine-tune",
+            ObjectType::FineTuneEvent => "fine-tune-event",
+            ObjectType::List => "list",
+            ObjectType::Custom(id) => id,
+        }
+    }
+}
+/// An empty [`ObjectType::Custom`], for responses created with `Default`
+impl Default for ObjectType {
+    fn default() -> Self {
+        ObjectType::Custom(String::new())
+    }
+}
+impl std::fmt::Display for ObjectType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+impl PartialEq<str> for ObjectType {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+impl PartialEq<&str> for ObjectType {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+impl Serialize for ObjectType {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+impl<'de> Deserialize<'de> for ObjectType {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let id = String::deserialize(deserializer)?;
+        Ok(ObjectType::from_id(&id))
+    }
+}
+
+/// The purpose of an uploaded file, which decides the endpoints it can be used with
+///
+/// Serialized as the id used by the api, e.g. `fine-tune`.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Purpose {
     Search,
     Answers,
     Classifications,
-    #[serde(rename = "fine-tune")]
     FineTuning,
+    /// The results of a fine-tune job, created by the api
+    FineTuneResults,
+    /// Any other purpose, identified by its id, for purposes added to the api after this crate
+    Custom(String),
 }
-impl ToString for Purpose {
-    fn to_string(&self) -> String {
+impl Purpose {
+    /// Creates a purpose from its id, falling back to [`Purpose::Custom`] for unknown ids
+    pub fn from_id(id: &str) -> Self {
+        match id {
+            "search" => Purpose::Search,
+            "answers" => Purpose::Answers,
+            "classifications" => Purpose::Classifications,
+            "fine-tune" => Purpose::FineTuning,
+            "fine-tune-results" => Purpose::FineTuneResults,
+            id => Purpose::Custom(id.to_string()),
+        }
+    }
+    /// The id of the purpose as it is sent to the api
+    #[must_use]
+    pub fn id(&self) -> &str {
         match self {
             Purpose::Search => "search",
             Purpose::Answers => "answers",
             Purpose::Classifications => "classifications",
             Purpose::FineTuning => "fine-tune",
+            Purpose::FineTuneResults => "fine-tune-results",
+            Purpose::Custom(id) => id,
         }
-        .to_string()
+    }
+}
+impl std::fmt::Display for Purpose {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.id())
+    }
+}
+impl Serialize for Purpose {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.id())
+    }
+}
+impl<'de> Deserialize<'de> for Purpose {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let id = String::deserialize(deserializer)?;
+        Ok(Purpose::from_id(&id))
+    }
+}
+
+/// Hashes an end-user id with sha256, used by the `user_hash` methods of the builders to set `user`
+///
+/// The same id always results in the same hash, so OpenAI can still tell your users apart
+/// without the request containing identifiable information like an email address.
+pub fn hash_user(raw: &str) -> String {
+    use sha2::{Digest, Sha256};
+
+    Sha256::digest(raw.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Adds a `user_hash` method to the builder of a request with a `user` field
+macro_rules! user_hash {
+    () => {
+        /// Sets [`user`](Self::user()) to the [`hash_user`](crate::api::hash_user) of `raw`
+        pub fn user_hash(&mut self, raw: &str) -> &mut Self {
+            self.user(crate::api::hash_user(raw))
+        }
+    };
+}
+pub(crate) use user_hash;
+
+/// Checks that a value set on a builder is within `min..=max`, used to validate builders before building
+pub(crate) fn check_range<T: Copy + PartialOrd + std::fmt::Display>(
+    name: &str,
+    value: Option<Option<T>>,
+    min: T,
+    max: T,
+) -> Result<(), String> {
+    match value.flatten() {
+        Some(value) if !(min..=max).contains(&value) => Err(format!(
+            "`{name}` must be between {min} and {max}, got {value}"
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Checks that every value of a `logit_bias` set on a builder is within `-100..=100`
+pub(crate) fn check_logit_bias(
+    logit_bias: &Option<Option<HashMap<String, i16>>>,
+) -> Result<(), String> {
+    let Some(Some(logit_bias)) = logit_bias else {
+        return Ok(());
+    };
+    match logit_bias
+        .iter()
+        .find(|(_, bias)| !(-100..=100).contains(*bias))
+    {
+        Some((token, bias)) => Err(format!(
+            "the `logit_bias` of token {token} must be between -100 and 100, got {bias}"
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Checks that at most one of two alternative builder fields is set,
+/// or exactly one if `required`
+pub(crate) fn check_exclusive(
+    (a, a_set): (&str, bool),
+    (b, b_set): (&str, bool),
+    required: bool,
+) -> Result<(), String> {
+    match (a_set, b_set) {
+        (true, true) => Err(format!("only one of `{a}` and `{b}` can be set")),
+        (false, false) if required => Err(format!("one of `{a}` and `{b}` must be set")),
+        _ => Ok(()),
     }
 }
 
@@ -49,9 +405,84 @@ impl ToString for Purpose {
 pub trait BuildRequest {
     fn build_request(&self, client: &Client) -> crate::RequestBuilder;
 }
-#[doc(hidden)]
+/// Adds the authentication headers of the client to a request
+pub(crate) trait Auth {
+    fn auth(self, client: &Client) -> Self;
+}
+impl Auth for crate::RequestBuilder {
+    fn auth(self, client: &Client) -> Self {
+        let builder = self.bearer_auth(client.gpt_token());
+        match client.organization() {
+            Some(organization) => builder.header("OpenAI-Organization", organization),
+            None => builder,
+        }
+    }
+}
+/// The maximum number of pages requested by the `list_all` helpers of list endpoints
+pub(crate) const MAX_PAGES: usize = 100;
+
+/// A request of a list endpoint that is paginated with an `after` cursor
+pub(crate) trait Paginated: crate::client::Request + Clone + Sync {
+    /// Sets the cursor, the id of the last item of the previous page
+    fn set_after(&mut self, after: String);
+}
+/// A page of a list endpoint
+pub(crate) trait Page {
+    /// The id of the last item, the cursor of the next page
+    fn last_id(&self) -> Option<String>;
+    /// Whether there are more items after this page
+    fn has_more(&self) -> bool;
+    /// Appends the items of the next page, taking over whether there are more
+    fn append(&mut self, next: Self);
+}
+/// Requests the pages starting at the cursor of `request` until the last one, at most [`MAX_PAGES`],
+/// and joins them into one page, which still has more items if the cap was hit
+#[cfg(not(feature = "blocking"))]
+pub(crate) async fn list_all<R>(client: &Client, request: &R) -> Result<R::Response, ClientError>
+where
+    R: Paginated,
+    R::Response: Page + Send,
+{
+    let mut request = request.clone();
+    let mut all = client.request(&request).await?;
+    for _ in 1..MAX_PAGES {
+        match all.last_id() {
+            Some(after) if all.has_more() => request.set_after(after),
+            _ => break,
+        }
+        all.append(client.request(&request).await?);
+    }
+    Ok(all)
+}
+/// Requests the pages starting at the cursor of `request` until the last one, at most [`MAX_PAGES`],
+/// and joins them into one page, which still has more items if the cap was hit
+#[cfg(feature = "blocking")]
+pub(crate) fn list_all<R>(client: &Client, request: &R) -> Result<R::Response, ClientError>
+where
+    R: Paginated,
+    R::Response: Page,
+{
+    let mut request = request.clone();
+    let mut all = client.request(&request)?;
+    for _ in 1..MAX_PAGES {
+        match all.last_id() {
+            Some(after) if all.has_more() => request.set_after(after),
+            _ => break,
+        }
+        all.append(client.request(&request)?);
+    }
+    Ok(all)
+}
+
+/// A request that is sent as a json `POST` to [`url`](RequestInfo::url)
 pub trait RequestInfo {
-    fn url(&self) -> String;
+    /// The url of the endpoint, relative to the `base_url` of the client
+    fn url(&self, base_url: &str) -> String;
+    /// Whether the model is part of the [`url`](RequestInfo::url),
+    /// it's then removed from the body that is sent
+    fn model_in_url(&self) -> bool {
+        false
+    }
 }
 
 impl<T> BuildRequest for T
@@ -59,10 +490,116 @@ where
     T: RequestInfo + Serialize,
 {
     fn build_request(&self, client: &Client) -> crate::RequestBuilder {
-        client
+        let builder = client
             .reqwest_client()
-            .post(&self.url())
-            .bearer_auth(client.gpt_token())
-            .json(self)
+            .post(self.url(client.base_url()))
+            .auth(client);
+        if self.model_in_url() {
+            if let Ok(serde_json::Value::Object(mut body)) = serde_json::to_value(self) {
+                body.remove("model");
+                return builder.json(&body);
+            }
+        }
+        builder.json(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LogProbs, ObjectType, Purpose};
+
+    #[test]
+    fn integer_logprobs() {
+        // captured from an echoed completion of "Hello world", trimmed to the logprobs
+        let json = r#"{
+            "tokens": ["Hello", " world", "!"],
+            "token_logprobs": [null, -7.2183127, 0],
+            "top_logprobs": [null, {" world": -7.2183127, ",": -1}, {"!": 0}],
+            "text_offset": [0, 5, 11]
+        }"#;
+
+        let logprobs: LogProbs = serde_json::from_str(json).unwrap();
+
+        assert_eq!(logprobs.token_logprobs, [None, Some(-7.2183127), Some(0.0)]);
+        assert_eq!(logprobs.top_at(1).unwrap()[","], -1.0);
+        assert_eq!(logprobs.top_at(2).unwrap()["!"], 0.0);
+        assert!(serde_json::from_str::<LogProbs>(
+            &json.replace("-7.2183127, 0]", r#"-7.2183127, "0"]"#)
+        )
+        .is_err());
+    }
+    #[test]
+    fn object_types() {
+        for object in [
+            ObjectType::TextCompletion,
+            ObjectType::ChatCompletionChunk,
+            ObjectType::FineTuneEvent,
+            ObjectType::Custom("assistant".to_string()),
+        ] {
+            let json = serde_json::to_string(&object).unwrap();
+            assert_eq!(json, format!("\"{object}\""));
+            assert_eq!(serde_json::from_str::<ObjectType>(&json).unwrap(), object);
+        }
+        assert_eq!(ObjectType::from_id("fine-tune"), ObjectType::FineTune);
+        assert_eq!(ObjectType::Custom("assistant".to_string()), "assistant");
+    }
+    #[test]
+    fn purpose_ids() {
+        assert_eq!(
+            serde_json::to_string(&Purpose::FineTuning).unwrap(),
+            r#""fine-tune""#
+        );
+        assert_eq!(
+            serde_json::from_str::<Purpose>(r#""fine-tune-results""#).unwrap(),
+            Purpose::FineTuneResults
+        );
+
+        let custom: Purpose = serde_json::from_str(r#""assistants""#).unwrap();
+        assert_eq!(custom, Purpose::Custom("assistants".to_string()));
+        assert_eq!(serde_json::to_string(&custom).unwrap(), r#""assistants""#);
+        assert_eq!(custom.to_string(), "assistants");
+    }
+
+    #[test]
+    fn logprobs_round_trip() {
+        let json = r#"{
+            "tokens": ["\n", "\n", "This", " is", " a", " test"],
+            "token_logprobs": [null, -0.12, -2.79, -0.48, -0.03, -0.91],
+            "top_logprobs": [
+                null,
+                {"\n": -0.12, "This": -3.6},
+                {"This": -2.79, "The": -2.81},
+                {" is": -0.48},
+                {" a": -0.03},
+                {" test": -0.91, " sentence": -2.6}
+            ],
+            "text_offset": [18, 19, 20, 24, 27, 29]
+        }"#;
+
+        let logprobs: LogProbs = serde_json::from_str(json).unwrap();
+
+        assert_eq!(logprobs.tokens.len(), 6);
+        assert_eq!(logprobs.token_logprobs[0], None);
+        assert_eq!(logprobs.token_logprobs[2], Some(-2.79));
+        assert_eq!(logprobs.top_logprobs[0], None);
+        assert_eq!(
+            logprobs.top_logprobs[5].as_ref().unwrap()[" sentence"],
+            -2.6
+        );
+        assert_eq!(logprobs.text_offset[5], 29);
+
+        let most_likely = logprobs.most_likely();
+        assert_eq!(most_likely.len(), 6);
+        assert_eq!(most_likely[0], ("\n".to_string(), None));
+        assert_eq!(most_likely[2], ("This".to_string(), Some(-2.79)));
+        assert_eq!(logprobs.top_at(0), None);
+        assert_eq!(logprobs.top_at(2).unwrap()["The"], -2.81);
+        assert_eq!(logprobs.top_at(6), None);
+
+        let round_trip = serde_json::to_string(&logprobs).unwrap();
+        assert_eq!(
+            serde_json::from_str::<LogProbs>(&round_trip).unwrap(),
+            logprobs
+        );
     }
 }
@@ -0,0 +1,77 @@
+//! Classify if text violates OpenAI's content policy
+//! # Builder
+//! Use the [`moderations::Builder`][struct@Builder] to construct a [`moderations::Request`][Request] struct
+use std::collections::HashMap;
+
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use crate::into_vec::IntoVec;
+
+use super::RequestInfo;
+/// Classify if text violates OpenAI's content policy
+///
+/// # OpenAi documentation
+/// Given a input text, outputs if the model classifies it as violating OpenAI's content policy.
+/// # Example
+/// ```ignore
+/// let request = moderations::Builder::default()
+///     .input(&["I want to kill them."])
+///     .build()
+///     .unwrap();
+/// ```
+/// # Required
+/// ```ignore
+/// input
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Builder)]
+#[builder_struct_attr(doc = "# Required")]
+#[builder_struct_attr(doc = "[`input`][Self::input()]")]
+#[builder_struct_attr(doc = "")]
+#[builder(name = "Builder")]
+pub struct Request {
+    /// The input text to classify
+    #[builder(setter(into))]
+    pub input: IntoVec<String>,
+    /// Two content moderations models are available: `text-moderation-stable` and `text-moderation-latest`.
+    /// The default is `text-moderation-latest` which will be automatically upgraded over time.
+    /// This ensures you are always using our most accurate model.
+    /// If you use `text-moderation-stable`, we will provide advanced notice before updating the model.
+    /// Accuracy of `text-moderation-stable` may be slightly lower than for `text-moderation-latest`.
+    #[builder(default, setter(strip_option, into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+}
+/// A response corresponding to a [`Request`]
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Response {
+    /// moderation id
+    pub id: String,
+    /// The model used for the moderation
+    pub model: String,
+    /// The results, one per input
+    pub results: Vec<ModerationResult>,
+    /// Fields returned by the api that aren't modeled by this crate yet
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModerationResult {
+    /// Whether the input violates the content policy
+    pub flagged: bool,
+    /// Whether the input violates a category, e.g. `hate` or `violence/graphic`
+    pub categories: HashMap<String, bool>,
+    /// The model's confidence per category
+    pub category_scores: HashMap<String, f64>,
+}
+
+impl RequestInfo for Request {
+    fn url(&self, base_url: &str) -> String {
+        format!("{base_url}/moderations")
+    }
+}
+#[cfg_attr(not(feature = "blocking"), async_trait::async_trait)]
+impl crate::client::Request for Request {
+    type Response = Response;
+}
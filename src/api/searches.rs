@@ -2,12 +2,14 @@
 //!
 //! # Builder
 //! Use the [`searches::Builder`][struct@Builder] to construct a [`searches::Request`][Request] struct
+use std::collections::HashMap;
+
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
 use crate::{into_vec::IntoVec, model::Model};
 
-use super::RequestInfo;
+use super::{user_hash, ObjectType, RequestInfo};
 /// Rank provided documents based off of a query
 ///
 /// # OpenAi documentation
@@ -29,16 +31,18 @@ use super::RequestInfo;
 /// ```
 /// # Required
 ///
-/// `model`, `query
+/// `model`, `query`
 ///
-#[derive(Debug, Clone, PartialEq, Serialize, Builder)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Builder)]
 #[builder_struct_attr(doc = "# Required")]
 #[builder_struct_attr(doc = "[`model`][Self::model()]")]
 #[builder_struct_attr(doc = "[`query`][Self::query()]")]
 #[builder_struct_attr(doc = "")]
 #[builder(name = "Builder")]
 pub struct Request {
-    #[serde(skip_serializing)]
+    /// The model is part of the url instead of the body, it's only serialized to persist the request
+    /// and deserializes to the default model if it's missing.
+    #[serde(default)]
     pub model: Model,
     /// Query to search against the documents.
     #[builder(setter(into))]
@@ -65,6 +69,7 @@ pub struct Request {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub return_metadata: Option<bool>,
     /// A unique identifier representing your end-user, which will help OpenAI to monitor and detect abuse.
+    /// It should not contain identifiable information, see [`Builder::user_hash`].
     #[builder(default, setter(strip_option, into))]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub user: Option<String>,
@@ -74,10 +79,13 @@ pub struct Request {
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Response {
     /// The requested action
-    pub object: Option<String>,
+    pub object: Option<ObjectType>,
     /// The ansers returned by this request
     pub data: Vec<Data>,
     pub model: Option<String>,
+    /// Fields returned by the api that aren't modeled by this crate yet
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -85,17 +93,76 @@ pub struct Data {
     /// The Document index a match was found in
     pub document: usize,
     /// The requested action
-    pub object: String,
+    pub object: ObjectType,
     /// The score / certainty that this is a match
     pub score: f64,
+    /// The text of the document, only returned when searching a file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// The metadata of the document, only returned when [`return_metadata`](Request::return_metadata) is set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<String>,
 }
 
+impl Builder {
+    user_hash!();
+}
 impl RequestInfo for Request {
-    fn url(&self) -> String {
-        self.model.url("/search")
+    fn url(&self, base_url: &str) -> String {
+        self.model.url(base_url, "/search")
+    }
+    fn model_in_url(&self) -> bool {
+        true
     }
 }
 #[cfg_attr(not(feature = "blocking"), async_trait::async_trait)]
 impl crate::client::Request for Request {
     type Response = Response;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Builder, Response};
+    use crate::{Model, OPENAI_URL};
+
+    #[test]
+    fn engine_scoped() {
+        let request = Builder::default()
+            .model(Model::Ada)
+            .query("the president")
+            .file("file-abc123")
+            .return_metadata(true)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            crate::api::RequestInfo::url(&request, OPENAI_URL),
+            "https://api.openai.com/v1/engines/text-ada-001/search"
+        );
+        let client = crate::Client::new("token".to_string());
+        let built = crate::api::BuildRequest::build_request(&request, &client)
+            .build()
+            .unwrap();
+        let json: serde_json::Value =
+            serde_json::from_slice(built.body().unwrap().as_bytes().unwrap()).unwrap();
+        assert!(json.get("model").is_none());
+        assert_eq!(json["file"], "file-abc123");
+        assert_eq!(serde_json::to_value(&request).unwrap()["model"], "ada");
+    }
+    #[test]
+    fn deserialize_file_results() {
+        let json = r#"{
+            "object": "list",
+            "data": [
+                {"document": 0, "object": "search_result", "score": 215.412, "text": "White house", "metadata": "building"},
+                {"document": 1, "object": "search_result", "score": 40.312}
+            ]
+        }"#;
+
+        let response: Response = serde_json::from_str(json).unwrap();
+
+        assert_eq!(response.data[0].text.as_deref(), Some("White house"));
+        assert_eq!(response.data[0].metadata.as_deref(), Some("building"));
+        assert_eq!(response.data[1].text, None);
+    }
+}
@@ -1,40 +1,161 @@
 use crate::api::BuildRequest;
+use crate::error::{ApiError, ClientError};
+use crate::hooks::{redact, RequestHook, RequestLog, ResponseHook, ResponseLog};
+use crate::into_vec::IntoVec;
+use crate::rate_limit::RateLimitInfo;
+use crate::retry::RetryPolicy;
+use crate::stream::ChunkStream;
+use crate::transport::Transport;
+use crate::HttpRequest;
+use crate::OPENAI_URL;
 #[cfg(not(feature = "blocking"))]
 use async_trait::async_trait;
-use serde::de::DeserializeOwned;
+use bytes::Bytes;
+use serde::{de::DeserializeOwned, Deserialize};
+use std::sync::Arc;
+use std::time::Duration;
 
 #[cfg(not(feature = "blocking"))]
 type RequestClient = reqwest::Client;
 #[cfg(feature = "blocking")]
 type RequestClient = reqwest::blocking::Client;
 
+#[cfg(not(feature = "blocking"))]
+type RequestClientBuilder = reqwest::ClientBuilder;
+#[cfg(feature = "blocking")]
+type RequestClientBuilder = reqwest::blocking::ClientBuilder;
+
 /// A client for interacting with the OpenAi api
 /// # Example
 /// ```ignore
 /// let client = Client::new(token);
 /// let request = completions::Builder::default()
 ///     .model(Model::Babbage)
-///     .prompt("what is 1 + 2?".into())
+///     .prompt("what is 1 + 2?")
 ///     .build()
 ///     .unwrap();
 ///
 /// let response = request.request(&client).await.unwrap();
 /// ```
+/// # Sharing
+/// The client holds a connection pool, so it should be created once and reused.
+/// Cloning it is cheap and the clones share the pool, e.g. to move a client into spawned tasks.
+/// ```ignore
+/// let client = Client::new(token);
+/// for request in requests {
+///     let client = client.clone();
+///     tokio::spawn(async move { request.request(&client).await });
+/// }
+/// ```
+#[derive(Clone)]
 pub struct Client {
     reqwest_client: RequestClient,
     gpt_token: String,
+    organization: Option<String>,
+    base_url: String,
+    retry_policy: RetryPolicy,
+    on_request: Option<RequestHook>,
+    on_response: Option<ResponseHook>,
+    on_large_body: Option<(usize, RequestHook)>,
+    moderate_input: bool,
+    transport: Arc<dyn Transport>,
 }
+/// The environment variable read by [`Client::from_env`]
+const API_KEY_VAR: &str = "OPENAI_API_KEY";
+
 impl Client {
     /// Creates a new client to send requests from
     /// # Example
     /// ```ignore
-    /// let token = std::env::var("GPT_API_TOKEN").unwrap();
-    /// let client = Client::new(token);
+    /// let client = Client::new("sk-...");
     /// ```
-    pub fn new(token: String) -> Self {
+    pub fn new(token: impl Into<String>) -> Self {
+        Client::with_reqwest(token, RequestClient::new())
+    }
+    /// Creates a new client that sends its requests with an already configured reqwest client,
+    /// e.g. with a proxy or custom root certificates
+    ///
+    /// With the `blocking` feature this is a `reqwest::blocking::Client`.
+    /// # Example
+    /// ```ignore
+    /// let reqwest_client = reqwest::Client::builder()
+    ///     .proxy(reqwest::Proxy::https("http://proxy.internal:8080")?)
+    ///     .build()?;
+    /// let client = Client::with_reqwest(token, reqwest_client);
+    /// ```
+    pub fn with_reqwest(token: impl Into<String>, reqwest_client: RequestClient) -> Self {
         Client {
-            reqwest_client: RequestClient::new(),
-            gpt_token: token,
+            transport: Arc::new(reqwest_client.clone()),
+            reqwest_client,
+            gpt_token: token.into(),
+            organization: None,
+            base_url: OPENAI_URL.to_string(),
+            retry_policy: RetryPolicy::none(),
+            on_request: None,
+            on_response: None,
+            on_large_body: None,
+            moderate_input: false,
+        }
+    }
+    /// Creates a new client with the token in the `OPENAI_API_KEY` environment variable
+    ///
+    /// Fails with [`ClientError::MissingApiKey`] if the variable isn't set.
+    /// # Example
+    /// ```ignore
+    /// let client = Client::from_env()?;
+    /// ```
+    pub fn from_env() -> Result<Self, ClientError> {
+        Self::from_api_key(std::env::var(API_KEY_VAR).ok())
+    }
+    /// Creates a new client with the value of the `OPENAI_API_KEY` environment variable, `None` if it isn't set
+    fn from_api_key(api_key: Option<String>) -> Result<Self, ClientError> {
+        api_key.map(Client::new).ok_or(ClientError::MissingApiKey)
+    }
+    /// Creates a new client that bills its requests to the given organization
+    /// # Example
+    /// ```ignore
+    /// let client = Client::with_organization(token, "org-123".to_string());
+    /// ```
+    pub fn with_organization(token: impl Into<String>, organization: impl Into<String>) -> Self {
+        Client {
+            organization: Some(organization.into()),
+            ..Client::new(token)
+        }
+    }
+    /// Creates a new client that sends its requests with the transport instead of reqwest,
+    /// e.g. a [`MockTransport`](crate::transport::MockTransport) in tests
+    /// # Example
+    /// ```ignore
+    /// let transport = MockTransport::new().respond(200, r#"{"object": "list", "data": []}"#);
+    /// let client = Client::with_transport(token, transport);
+    /// ```
+    pub fn with_transport(token: impl Into<String>, transport: impl Transport + 'static) -> Self {
+        Client {
+            transport: Arc::new(transport),
+            ..Client::new(token)
+        }
+    }
+    /// Creates a builder to configure the client
+    /// # Example
+    /// ```ignore
+    /// let client = Client::builder(token)
+    ///     .timeout(Duration::from_secs(60))
+    ///     .connect_timeout(Duration::from_secs(5))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn builder(token: impl Into<String>) -> ClientBuilder {
+        ClientBuilder {
+            reqwest_builder: RequestClient::builder(),
+            gpt_token: token.into(),
+            organization: None,
+            base_url: OPENAI_URL.to_string(),
+            retry_policy: RetryPolicy::none(),
+            on_request: None,
+            on_response: None,
+            on_large_body: None,
+            moderate_input: false,
+            transport: None,
         }
     }
     /// Get a reference to the client's gpt token.
@@ -42,17 +163,767 @@ impl Client {
     pub fn gpt_token(&self) -> &str {
         self.gpt_token.as_ref()
     }
+    /// Get a reference to the organization the client's requests are billed to.
+    #[must_use]
+    pub fn organization(&self) -> Option<&str> {
+        self.organization.as_deref()
+    }
+    /// Get a reference to the url all requests are sent to, `https://api.openai.com/v1` by default.
+    #[must_use]
+    pub fn base_url(&self) -> &str {
+        self.base_url.as_ref()
+    }
+    /// Get the client's retry policy.
+    #[must_use]
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.retry_policy
+    }
+    /// Whether prompts are checked with the moderations endpoint before they are sent.
+    #[must_use]
+    pub fn moderate_input(&self) -> bool {
+        self.moderate_input
+    }
+    /// The body size in bytes above which [`ClientBuilder::on_large_body`] is called, `None` if it isn't set.
+    #[must_use]
+    pub fn large_body_threshold(&self) -> Option<usize> {
+        self.on_large_body.as_ref().map(|(threshold, _)| *threshold)
+    }
+    /// Checks that the api token is valid by listing the engines, e.g. to fail fast when an application starts
+    ///
+    /// A rejected token is returned as [`ClientError::Unauthorized`], other errors as usual.
+    /// # Example
+    /// ```ignore
+    /// let client = Client::from_env()?;
+    /// client.validate_key().await?;
+    /// ```
+    #[cfg(not(feature = "blocking"))]
+    pub async fn validate_key(&self) -> Result<(), ClientError> {
+        match self.request(&crate::api::engines::list::Request).await {
+            Ok(_) => Ok(()),
+            Err(ClientError::Api(error)) if error.is_authentication() => {
+                Err(ClientError::Unauthorized(error))
+            }
+            Err(error) => Err(error),
+        }
+    }
+    /// Checks that the api token is valid by listing the engines, e.g. to fail fast when an application starts
+    ///
+    /// A rejected token is returned as [`ClientError::Unauthorized`], other errors as usual.
+    /// # Example
+    /// ```ignore
+    /// let client = Client::from_env()?;
+    /// client.validate_key()?;
+    /// ```
+    #[cfg(feature = "blocking")]
+    pub fn validate_key(&self) -> Result<(), ClientError> {
+        match self.request(&crate::api::engines::list::Request) {
+            Ok(_) => Ok(()),
+            Err(ClientError::Api(error)) if error.is_authentication() => {
+                Err(ClientError::Unauthorized(error))
+            }
+            Err(error) => Err(error),
+        }
+    }
+    /// Runs the input of the request through the moderations endpoint if [`moderate_input`](Self::moderate_input) is set
+    #[cfg(feature = "blocking")]
+    pub(crate) fn moderate<R: Request + ?Sized>(&self, request: &R) -> Result<(), ClientError> {
+        match request.moderation_input() {
+            Some(input) if self.moderate_input => {
+                let response = crate::api::moderations::Request {
+                    input: input.clone(),
+                    model: None,
+                }
+                .request(self)?;
+                check_moderation(response)
+            }
+            _ => Ok(()),
+        }
+    }
+    /// Runs the input of the request through the moderations endpoint if [`moderate_input`](Self::moderate_input) is set
+    #[cfg(not(feature = "blocking"))]
+    pub(crate) async fn moderate<R: Request + Sync + ?Sized>(
+        &self,
+        request: &R,
+    ) -> Result<(), ClientError> {
+        match request.moderation_input() {
+            Some(input) if self.moderate_input => {
+                let response = crate::api::moderations::Request {
+                    input: input.clone(),
+                    model: None,
+                }
+                .request(self)
+                .await?;
+                check_moderation(response)
+            }
+            _ => Ok(()),
+        }
+    }
+    /// Sends a request, retrying it according to the retry policy
+    /// and turning non success responses into a [`ClientError::Api`]
+    #[cfg(feature = "blocking")]
+    pub(crate) fn send(
+        &self,
+        builder: crate::RequestBuilder,
+    ) -> Result<crate::Response, ClientError> {
+        let mut request = builder.build()?;
+        let mut attempt = 0;
+        loop {
+            let retry = if attempt < self.retry_policy.max_retries {
+                request.try_clone()
+            } else {
+                None
+            };
+            self.log_request(&request);
+            let response = self.transport.send(request)?;
+            self.log_response(&response);
+            let Some(next) = retry else {
+                return check_status(response);
+            };
+            // the body of the error tells rate limits and an exhausted quota apart
+            let headers = response.headers().clone();
+            match check_status(response) {
+                Err(ClientError::Api(error)) if self.retry_policy.should_retry(&error) => {
+                    std::thread::sleep(self.retry_policy.delay(attempt, &headers));
+                    request = next;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+    /// Sends a request, retrying it according to the retry policy
+    /// and turning non success responses into a [`ClientError::Api`]
+    #[cfg(not(feature = "blocking"))]
+    pub(crate) async fn send(
+        &self,
+        builder: crate::RequestBuilder,
+    ) -> Result<crate::Response, ClientError> {
+        let mut request = builder.build()?;
+        let mut attempt = 0;
+        loop {
+            let retry = if attempt < self.retry_policy.max_retries {
+                request.try_clone()
+            } else {
+                None
+            };
+            self.log_request(&request);
+            let response = self.transport.send(request).await?;
+            self.log_response(&response);
+            let Some(next) = retry else {
+                return check_status(response).await;
+            };
+            // the body of the error tells rate limits and an exhausted quota apart
+            let headers = response.headers().clone();
+            match check_status(response).await {
+                Err(ClientError::Api(error)) if self.retry_policy.should_retry(&error) => {
+                    tokio::time::sleep(self.retry_policy.delay(attempt, &headers)).await;
+                    request = next;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+    fn log_request(&self, request: &HttpRequest) {
+        let body = request.body().and_then(|body| body.as_bytes());
+        let large_body = self
+            .on_large_body
+            .as_ref()
+            .filter(|(threshold, _)| body.map_or(0, <[u8]>::len) > *threshold);
+        if self.on_request.is_none() && large_body.is_none() {
+            return;
+        }
+        let log = RequestLog {
+            method: request.method(),
+            url: request.url(),
+            headers: redact(request.headers()),
+            body,
+        };
+        if let Some(on_request) = &self.on_request {
+            on_request(&log);
+        }
+        if let Some((_, on_large_body)) = large_body {
+            on_large_body(&log);
+        }
+    }
+    fn log_response(&self, response: &crate::Response) {
+        if let Some(on_response) = &self.on_response {
+            on_response(&ResponseLog {
+                status: response.status(),
+                url: response.url(),
+                headers: response.headers(),
+            });
+        }
+    }
     /// Get a reference to the client's request client.
     #[must_use]
     pub fn reqwest_client(&self) -> &RequestClient {
         &self.reqwest_client
     }
+    /// Sends the request and deserializes the response, the same as [`Request::request`]
+    /// # Example
+    /// ```ignore
+    /// let response = client.request(&request).await?;
+    /// ```
+    /// # Cancellation
+    /// The future holds no state outside of the request, so it is cancel safe.
+    /// Dropping it, e.g. in `tokio::select!` or when `tokio::time::timeout` elapses,
+    /// aborts the http call and no retry is sent afterwards.
+    /// This allows deadlines per request, in addition to [`ClientBuilder::timeout`].
+    /// ```ignore
+    /// match tokio::time::timeout(Duration::from_secs(5), client.request(&request)).await {
+    ///     Ok(response) => println!("{:?}", response?),
+    ///     Err(_) => println!("the request took longer than 5 seconds and was cancelled"),
+    /// }
+    /// ```
+    #[cfg(not(feature = "blocking"))]
+    pub async fn request<R>(&self, request: &R) -> Result<R::Response, ClientError>
+    where
+        R: Request + Sync,
+    {
+        request.request(self).await
+    }
+    /// Sends the request and deserializes the response, the same as [`Request::request`]
+    ///
+    /// Blocks the current thread until the response arrived, no async runtime is needed.
+    /// # Example
+    /// ```ignore
+    /// let response = client.request(&request)?;
+    /// ```
+    #[cfg(feature = "blocking")]
+    pub fn request<R>(&self, request: &R) -> Result<R::Response, ClientError>
+    where
+        R: Request,
+    {
+        request.request(self)
+    }
+    /// Sends the request and returns the response both deserialized and as the full json,
+    /// to access fields this crate doesn't know about yet
+    /// # Example
+    /// ```ignore
+    /// let (response, json) = client.request_with_json(&request).await?;
+    /// let service_tier = &json["service_tier"];
+    /// ```
+    #[cfg(not(feature = "blocking"))]
+    pub async fn request_with_json<R>(
+        &self,
+        request: &R,
+    ) -> Result<(R::Response, serde_json::Value), ClientError>
+    where
+        R: Request + Sync,
+    {
+        self.moderate(request).await?;
+        let text = self.send(request.build_request(self)).await?.text().await?;
+        parse_with_json::<R>(&text)
+    }
+    /// Sends the request and returns the response both deserialized and as the full json,
+    /// to access fields this crate doesn't know about yet
+    /// # Example
+    /// ```ignore
+    /// let (response, json) = client.request_with_json(&request)?;
+    /// let service_tier = &json["service_tier"];
+    /// ```
+    #[cfg(feature = "blocking")]
+    pub fn request_with_json<R>(
+        &self,
+        request: &R,
+    ) -> Result<(R::Response, serde_json::Value), ClientError>
+    where
+        R: Request,
+    {
+        self.moderate(request)?;
+        let text = self.send(request.build_request(self))?.text()?;
+        parse_with_json::<R>(&text)
+    }
+    /// Sends the request and returns the body as is, for endpoints that don't respond with json
+    /// like [`audio::speech`](crate::api::audio::speech) or [`files::content`](crate::api::files::content)
+    ///
+    /// Errors are still returned as [`ClientError::Api`].
+    /// # Example
+    /// ```ignore
+    /// let audio = client.request_bytes(&request).await?;
+    /// ```
+    #[cfg(not(feature = "blocking"))]
+    pub async fn request_bytes<R>(&self, request: &R) -> Result<Bytes, ClientError>
+    where
+        R: Request + Sync,
+    {
+        self.moderate(request).await?;
+        let bytes = self
+            .send(request.build_request(self))
+            .await?
+            .bytes()
+            .await?;
+        Ok(bytes)
+    }
+    /// Sends the request and returns the body as is, for endpoints that don't respond with json
+    /// like [`audio::speech`](crate::api::audio::speech) or [`files::content`](crate::api::files::content)
+    ///
+    /// Errors are still returned as [`ClientError::Api`].
+    /// # Example
+    /// ```ignore
+    /// let audio = client.request_bytes(&request)?;
+    /// ```
+    #[cfg(feature = "blocking")]
+    pub fn request_bytes<R>(&self, request: &R) -> Result<Bytes, ClientError>
+    where
+        R: Request,
+    {
+        self.moderate(request)?;
+        let bytes = self.send(request.build_request(self))?.bytes()?;
+        Ok(bytes)
+    }
+    /// Builds the request without sending it, to inspect what would be sent
+    /// # Example
+    /// ```ignore
+    /// let dry_run = client.dry_run(&request)?;
+    /// println!("{} {}", dry_run.method, dry_run.url);
+    /// println!("{:#}", dry_run.body.unwrap());
+    /// ```
+    pub fn dry_run<R>(&self, request: &R) -> Result<DryRun, ClientError>
+    where
+        R: BuildRequest,
+    {
+        let request = request.build_request(self).build()?;
+        let body = match request.body().and_then(|body| body.as_bytes()) {
+            Some(bytes) => serde_json::from_slice(bytes).ok(),
+            None => None,
+        };
+        Ok(DryRun {
+            method: request.method().clone(),
+            url: request.url().clone(),
+            body,
+        })
+    }
+    /// Sends all requests concurrently, with at most `concurrency` requests in flight at once
+    ///
+    /// The responses are returned in the same order as the requests.
+    /// # Example
+    /// ```ignore
+    /// let requests = prompts
+    ///     .iter()
+    ///     .map(|prompt| completions::Builder::default().model(Model::Curie).prompt(prompt).build())
+    ///     .collect::<Result<Vec<_>, _>>()?;
+    ///
+    /// for response in client.request_batch(requests, 4).await {
+    ///     println!("{}", response?.choices[0].text);
+    /// }
+    /// ```
+    #[cfg(not(feature = "blocking"))]
+    pub async fn request_batch<R>(
+        &self,
+        requests: Vec<R>,
+        concurrency: usize,
+    ) -> Vec<Result<R::Response, ClientError>>
+    where
+        R: Request + Sync,
+    {
+        use futures_util::StreamExt;
+
+        futures_util::stream::iter(&requests)
+            .map(|request| request.request(self))
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
+    /// Sends all requests concurrently, with at most `concurrency` requests in flight at once
+    ///
+    /// Each request in flight uses its own thread.
+    /// The responses are returned in the same order as the requests.
+    /// # Example
+    /// ```ignore
+    /// let requests = prompts
+    ///     .iter()
+    ///     .map(|prompt| completions::Builder::default().model(Model::Curie).prompt(prompt).build())
+    ///     .collect::<Result<Vec<_>, _>>()?;
+    ///
+    /// for response in client.request_batch(requests, 4) {
+    ///     println!("{}", response?.choices[0].text);
+    /// }
+    /// ```
+    #[cfg(feature = "blocking")]
+    pub fn request_batch<R>(
+        &self,
+        requests: Vec<R>,
+        concurrency: usize,
+    ) -> Vec<Result<R::Response, ClientError>>
+    where
+        R: Request + Sync,
+        R::Response: Send,
+    {
+        let mut responses = Vec::with_capacity(requests.len());
+        for batch in requests.chunks(concurrency.max(1)) {
+            std::thread::scope(|scope| {
+                let handles = batch
+                    .iter()
+                    .map(|request| scope.spawn(|| request.request(self)))
+                    .collect::<Vec<_>>();
+                responses.extend(
+                    handles
+                        .into_iter()
+                        .map(|handle| handle.join().expect("request thread panicked")),
+                );
+            });
+        }
+        responses
+    }
+    /// Sends the request authenticated with `token` instead of the client's token
+    ///
+    /// All other settings of the client are used as usual.
+    /// # Example
+    /// ```ignore
+    /// let response = client.request_with_auth(&request, &project_token).await?;
+    /// ```
+    #[cfg(not(feature = "blocking"))]
+    pub async fn request_with_auth<R>(
+        &self,
+        request: &R,
+        token: &str,
+    ) -> Result<R::Response, ClientError>
+    where
+        R: Request + Sync,
+    {
+        request.request(&self.with_token(token)).await
+    }
+    /// Sends the request authenticated with `token` instead of the client's token
+    ///
+    /// All other settings of the client are used as usual.
+    /// # Example
+    /// ```ignore
+    /// let response = client.request_with_auth(&request, &project_token)?;
+    /// ```
+    #[cfg(feature = "blocking")]
+    pub fn request_with_auth<R>(&self, request: &R, token: &str) -> Result<R::Response, ClientError>
+    where
+        R: Request,
+    {
+        request.request(&self.with_token(token))
+    }
+    /// A client sharing the connections and settings of this one, with a different token
+    fn with_token(&self, token: &str) -> Client {
+        Client {
+            gpt_token: token.to_string(),
+            ..self.clone()
+        }
+    }
+    /// Sends the request and returns the response together with the rate limits sent in its headers
+    ///
+    /// This can be used to slow down before the api responds with `429 Too Many Requests`.
+    /// # Example
+    /// ```ignore
+    /// let (response, rate_limit) = client.request_with_headers(&request).await?;
+    ///
+    /// if rate_limit.remaining_requests == Some(0) {
+    ///     tokio::time::sleep(rate_limit.reset_requests.unwrap_or_default()).await;
+    /// }
+    /// ```
+    #[cfg(not(feature = "blocking"))]
+    pub async fn request_with_headers<R>(
+        &self,
+        request: &R,
+    ) -> Result<(R::Response, RateLimitInfo), ClientError>
+    where
+        R: Request + Sync,
+    {
+        self.moderate(request).await?;
+        let response = self.send(request.build_request(self)).await?;
+        let rate_limit = RateLimitInfo::from_headers(response.headers());
+        let text = response.text().await?;
+        let response = serde_json::from_str(&text).map_err(ClientError::Deserialize)?;
+        Ok((response, rate_limit))
+    }
+    /// Sends the request and returns the response together with the rate limits sent in its headers
+    ///
+    /// This can be used to slow down before the api responds with `429 Too Many Requests`.
+    /// # Example
+    /// ```ignore
+    /// let (response, rate_limit) = client.request_with_headers(&request)?;
+    ///
+    /// if rate_limit.remaining_requests == Some(0) {
+    ///     std::thread::sleep(rate_limit.reset_requests.unwrap_or_default());
+    /// }
+    /// ```
+    #[cfg(feature = "blocking")]
+    pub fn request_with_headers<R>(
+        &self,
+        request: &R,
+    ) -> Result<(R::Response, RateLimitInfo), ClientError>
+    where
+        R: Request,
+    {
+        self.moderate(request)?;
+        let response = self.send(request.build_request(self))?;
+        let rate_limit = RateLimitInfo::from_headers(response.headers());
+        let text = response.text()?;
+        let response = serde_json::from_str(&text).map_err(ClientError::Deserialize)?;
+        Ok((response, rate_limit))
+    }
+    /// Sends the request with `stream` enabled and returns the chunks as they arrive
+    ///
+    /// Dropping the stream cancels the request, the connection is closed and no more tokens are generated.
+    /// # Example
+    /// ```ignore
+    /// let mut stream = client.request_stream(&request).await?;
+    ///
+    /// while let Some(chunk) = stream.next().await {
+    ///     print!("{}", chunk?.choices[0].text);
+    /// }
+    /// ```
+    #[cfg(not(feature = "blocking"))]
+    pub async fn request_stream<R>(&self, request: &R) -> Result<ChunkStream<R::Chunk>, ClientError>
+    where
+        R: StreamRequest + Sync,
+    {
+        self.moderate(request).await?;
+        let response = self.send(request.streaming().build_request(self)).await?;
+        Ok(crate::stream::chunks(response.bytes_stream()))
+    }
+    /// Sends the request with `stream` enabled and returns the chunks as they arrive
+    ///
+    /// Dropping the iterator cancels the request, the connection is closed and no more tokens are generated.
+    /// # Example
+    /// ```ignore
+    /// for chunk in client.request_stream(&request)? {
+    ///     print!("{}", chunk?.choices[0].text);
+    /// }
+    /// ```
+    #[cfg(feature = "blocking")]
+    pub fn request_stream<R>(&self, request: &R) -> Result<ChunkStream<R::Chunk>, ClientError>
+    where
+        R: StreamRequest,
+    {
+        self.moderate(request)?;
+        let response = self.send(request.streaming().build_request(self))?;
+        Ok(crate::stream::chunks(response))
+    }
+    /// Streams the request and concatenates the text of all chunks,
+    /// for the lower latency of streaming without handling the chunks
+    ///
+    /// Only the text of the first choice is collected, see [`StreamRequest::chunk_text`].
+    /// # Example
+    /// ```ignore
+    /// let text = client.request_stream_collect(&request).await?;
+    /// ```
+    #[cfg(not(feature = "blocking"))]
+    pub async fn request_stream_collect<R>(&self, request: &R) -> Result<String, ClientError>
+    where
+        R: StreamRequest + Sync,
+    {
+        use futures_util::StreamExt;
+
+        let mut stream = self.request_stream(request).await?;
+        let mut text = String::new();
+        while let Some(chunk) = stream.next().await {
+            text.push_str(R::chunk_text(&chunk?));
+        }
+        Ok(text)
+    }
+    /// Streams the request and concatenates the text of all chunks,
+    /// for the lower latency of streaming without handling the chunks
+    ///
+    /// Only the text of the first choice is collected, see [`StreamRequest::chunk_text`].
+    /// # Example
+    /// ```ignore
+    /// let text = client.request_stream_collect(&request)?;
+    /// ```
+    #[cfg(feature = "blocking")]
+    pub fn request_stream_collect<R>(&self, request: &R) -> Result<String, ClientError>
+    where
+        R: StreamRequest,
+    {
+        let mut text = String::new();
+        for chunk in self.request_stream(request)? {
+            text.push_str(R::chunk_text(&chunk?));
+        }
+        Ok(text)
+    }
+}
+
+/// A request that was built but not sent, returned by [`Client::dry_run`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DryRun {
+    /// The http method of the request
+    pub method: reqwest::Method,
+    /// The url the request would be sent to
+    pub url: reqwest::Url,
+    /// The json body of the request, `None` for requests without a json body like file uploads
+    pub body: Option<serde_json::Value>,
+}
+
+/// A builder to configure a [`Client`], created with [`Client::builder`]
+pub struct ClientBuilder {
+    reqwest_builder: RequestClientBuilder,
+    gpt_token: String,
+    organization: Option<String>,
+    base_url: String,
+    retry_policy: RetryPolicy,
+    on_request: Option<RequestHook>,
+    on_response: Option<ResponseHook>,
+    on_large_body: Option<(usize, RequestHook)>,
+    moderate_input: bool,
+    transport: Option<Arc<dyn Transport>>,
+}
+impl ClientBuilder {
+    /// Sets the organization requests are billed to, sent as the `OpenAI-Organization` header
+    pub fn organization(mut self, organization: String) -> Self {
+        self.organization = Some(organization);
+        self
+    }
+    /// Sets the url requests are sent to instead of `https://api.openai.com/v1`,
+    /// e.g. to use a proxy or a compatible api
+    /// # Example
+    /// ```ignore
+    /// let client = Client::builder(token)
+    ///     .base_url("http://localhost:8080/v1".to_string())
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn base_url(mut self, base_url: String) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+    /// Sets how rate limited and failed requests are retried, by default requests aren't retried
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+    /// Sets a callback that is called before every request is sent, including retries
+    ///
+    /// The `Authorization` header is redacted, so the api token is never passed to the callback.
+    /// # Example
+    /// ```ignore
+    /// let client = Client::builder(token)
+    ///     .on_request(|request| println!("{} {}", request.method, request.url))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn on_request<F>(mut self, on_request: F) -> Self
+    where
+        F: Fn(&RequestLog<'_>) + Send + Sync + 'static,
+    {
+        self.on_request = Some(Arc::new(on_request));
+        self
+    }
+    /// Sets a callback that is called for every response received, including responses that are retried
+    pub fn on_response<F>(mut self, on_response: F) -> Self
+    where
+        F: Fn(&ResponseLog<'_>) + Send + Sync + 'static,
+    {
+        self.on_response = Some(Arc::new(on_response));
+        self
+    }
+    /// Sets a callback that is called before a request with a body larger than `threshold` bytes is sent,
+    /// e.g. to warn about classifications with thousands of examples that slow down every request
+    ///
+    /// Only json bodies are checked, file uploads are streamed and never passed to the callback.
+    /// # Example
+    /// ```ignore
+    /// let client = Client::builder(token)
+    ///     .on_large_body(1024 * 1024, |request| {
+    ///         eprintln!("sending {} bytes to {}", request.body.unwrap().len(), request.url)
+    ///     })
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn on_large_body<F>(mut self, threshold: usize, on_large_body: F) -> Self
+    where
+        F: Fn(&RequestLog<'_>) + Send + Sync + 'static,
+    {
+        self.on_large_body = Some((threshold, Arc::new(on_large_body)));
+        self
+    }
+    /// Sets the transport requests are sent with instead of reqwest,
+    /// e.g. a [`MockTransport`](crate::transport::MockTransport) in tests
+    ///
+    /// The timeouts of the builder only apply to the default transport.
+    pub fn transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+    /// Checks the prompts of completions with the moderations endpoint before sending them, off by default
+    ///
+    /// Flagged prompts aren't sent and return a [`ClientError::Moderated`] instead.
+    /// This costs an additional request per completion.
+    /// # Example
+    /// ```ignore
+    /// let client = Client::builder(token).moderate_input(true).build().unwrap();
+    ///
+    /// match request.request(&client).await {
+    ///     Err(ClientError::Moderated(categories)) => println!("flagged for {categories:?}"),
+    ///     _ => (),
+    /// }
+    /// ```
+    pub fn moderate_input(mut self, moderate_input: bool) -> Self {
+        self.moderate_input = moderate_input;
+        self
+    }
+    /// Sets a timeout for the whole request, from connecting until the response body has been read.
+    ///
+    /// For streamed requests this includes reading the whole stream.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.reqwest_builder = self.reqwest_builder.timeout(timeout);
+        self
+    }
+    /// Sets whether responses are requested compressed and decompressed,
+    /// enabled by default with the `compression` feature
+    ///
+    /// This reduces the transferred size of large responses, e.g. batches of embeddings.
+    #[cfg(feature = "compression")]
+    pub fn compression(mut self, enabled: bool) -> Self {
+        self.reqwest_builder = self
+            .reqwest_builder
+            .gzip(enabled)
+            .brotli(enabled)
+            .deflate(enabled);
+        self
+    }
+    /// Sets the maximum number of idle connections kept open per host, by default there is no limit
+    ///
+    /// All requests go to the same host, so this is the number of connections kept for reuse between requests.
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.reqwest_builder = self.reqwest_builder.pool_max_idle_per_host(max);
+        self
+    }
+    /// Sets how long idle connections are kept open, 90 seconds by default
+    ///
+    /// `None` keeps them open until the server closes them.
+    pub fn pool_idle_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.reqwest_builder = self.reqwest_builder.pool_idle_timeout(timeout);
+        self
+    }
+    /// Sets a timeout for only the connect phase of the request
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.reqwest_builder = self.reqwest_builder.connect_timeout(timeout);
+        self
+    }
+    /// Builds the client
+    ///
+    /// This fails if the underlying request client can't be initialized, e.g. if the tls backend fails to load.
+    pub fn build(self) -> Result<Client, ClientError> {
+        let reqwest_client = self.reqwest_builder.build()?;
+        Ok(Client {
+            transport: self
+                .transport
+                .unwrap_or_else(|| Arc::new(reqwest_client.clone())),
+            reqwest_client,
+            gpt_token: self.gpt_token,
+            organization: self.organization,
+            base_url: self.base_url,
+            retry_policy: self.retry_policy,
+            on_request: self.on_request,
+            on_response: self.on_response,
+            on_large_body: self.on_large_body,
+            moderate_input: self.moderate_input,
+        })
+    }
 }
 
 /// Sends the request to the OpenAi api
 ///
 /// [`request`][Request::request()] returns a deserialzed version of the response,
 /// while [`request_raw`][Request::request_raw()] returns a String
+///
+/// Responses with a non success status are returned as a [`ClientError::Api`]
 /// # Usage
 /// using a builder to build the request struct is strongly advised
 /// # Example
@@ -72,19 +943,24 @@ where
     Self::Response: DeserializeOwned,
 {
     type Response;
+    /// The text checked with the moderations endpoint if [`ClientBuilder::moderate_input`] is set,
+    /// `None` for requests that aren't moderated
+    fn moderation_input(&self) -> Option<&IntoVec<String>> {
+        None
+    }
     #[cfg(feature = "blocking")]
     fn request(
         &self,
         client: &Client,
-    ) -> reqwest::Result<<Self as crate::client::Request>::Response> {
-        let response = self.build_request(client).send()?;
-        let json = response.json()?;
-        Ok(json)
+    ) -> Result<<Self as crate::client::Request>::Response, ClientError> {
+        client.moderate(self)?;
+        let text = client.send(self.build_request(client))?.text()?;
+        serde_json::from_str(&text).map_err(ClientError::Deserialize)
     }
     #[cfg(feature = "blocking")]
-    fn request_raw(&self, client: &Client) -> reqwest::Result<String> {
-        let response = self.build_request(client).send()?;
-        let text = response.text()?;
+    fn request_raw(&self, client: &Client) -> Result<String, ClientError> {
+        client.moderate(self)?;
+        let text = client.send(self.build_request(client))?.text()?;
         Ok(text)
     }
 
@@ -92,15 +968,790 @@ where
     async fn request(
         &self,
         client: &Client,
-    ) -> reqwest::Result<<Self as crate::client::Request>::Response> {
-        let response = self.build_request(client).send().await?;
-        let json = response.json().await?;
-        Ok(json)
+    ) -> Result<<Self as crate::client::Request>::Response, ClientError> {
+        client.moderate(self).await?;
+        let response = client.send(self.build_request(client)).await?;
+        let text = response.text().await?;
+        serde_json::from_str(&text).map_err(ClientError::Deserialize)
     }
     #[cfg(not(feature = "blocking"))]
-    async fn request_raw(&self, client: &Client) -> reqwest::Result<String> {
-        let response = self.build_request(client).send().await?;
+    async fn request_raw(&self, client: &Client) -> Result<String, ClientError> {
+        client.moderate(self).await?;
+        let response = client.send(self.build_request(client)).await?;
         let text = response.text().await?;
         Ok(text)
     }
 }
+
+/// A request that can stream back partial progress
+///
+/// Used by [`Client::request_stream`]
+pub trait StreamRequest: Request
+where
+    Self::Response: DeserializeOwned,
+{
+    /// A partial response
+    type Chunk: DeserializeOwned + Send + 'static;
+    /// Returns a copy of the request with streaming enabled
+    fn streaming(&self) -> Self;
+    /// The text a chunk adds to the first choice, empty if the chunk belongs to another choice
+    ///
+    /// Used by [`Client::request_stream_collect`]
+    fn chunk_text(chunk: &Self::Chunk) -> &str;
+}
+
+/// Turns a flagged moderation into a [`ClientError::Moderated`] with the flagged categories
+fn check_moderation(response: crate::api::moderations::Response) -> Result<(), ClientError> {
+    let mut categories = response
+        .results
+        .into_iter()
+        .filter(|result| result.flagged)
+        .flat_map(|result| result.categories)
+        .filter_map(|(category, flagged)| flagged.then_some(category))
+        .collect::<Vec<_>>();
+    if categories.is_empty() {
+        return Ok(());
+    }
+    categories.sort();
+    categories.dedup();
+    Err(ClientError::Moderated(categories))
+}
+
+fn parse_with_json<R: Request>(
+    text: &str,
+) -> Result<(R::Response, serde_json::Value), ClientError> {
+    let json: serde_json::Value = serde_json::from_str(text).map_err(ClientError::Deserialize)?;
+    let response = R::Response::deserialize(&json).map_err(ClientError::Deserialize)?;
+    Ok((response, json))
+}
+
+fn request_id(headers: &reqwest::header::HeaderMap) -> Option<Box<str>> {
+    headers
+        .get("x-request-id")
+        .and_then(|value| value.to_str().ok())
+        .map(Box::from)
+}
+/// Turns a response with a non success status into a [`ClientError::Api`]
+#[cfg(feature = "blocking")]
+pub(crate) fn check_status(response: crate::Response) -> Result<crate::Response, ClientError> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let request_id = request_id(response.headers());
+    let body = response.text()?;
+    Err(ClientError::Api(ApiError {
+        request_id,
+        ..ApiError::from_body(status, &body)
+    }))
+}
+/// Turns a response with a non success status into a [`ClientError::Api`]
+#[cfg(not(feature = "blocking"))]
+pub(crate) async fn check_status(
+    response: crate::Response,
+) -> Result<crate::Response, ClientError> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let request_id = request_id(response.headers());
+    let body = response.text().await?;
+    Err(ClientError::Api(ApiError {
+        request_id,
+        ..ApiError::from_body(status, &body)
+    }))
+}
+
+#[cfg(all(test, not(feature = "blocking")))]
+mod async_tests {
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+    use std::time::Duration;
+
+    use crate::transport::Transport;
+    use crate::{api::completions, Client, ClientError, HttpRequest, Model};
+
+    /// Never responds, and records when the pending request was dropped
+    struct Slow {
+        dropped: Arc<AtomicBool>,
+    }
+    struct SetOnDrop(Arc<AtomicBool>);
+    impl Drop for SetOnDrop {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+    #[async_trait::async_trait]
+    impl Transport for Slow {
+        async fn send(&self, _: HttpRequest) -> Result<crate::Response, ClientError> {
+            let _guard = SetOnDrop(self.dropped.clone());
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            unreachable!("the request is cancelled before the sleep ends")
+        }
+    }
+
+    #[tokio::test]
+    async fn timeout_cancels_request() {
+        let dropped = Arc::new(AtomicBool::new(false));
+        let client = Client::with_transport(
+            "token",
+            Slow {
+                dropped: dropped.clone(),
+            },
+        );
+        let request = completions::Builder::default()
+            .model(Model::Ada)
+            .prompt("Say this is a test")
+            .build()
+            .unwrap();
+
+        let result =
+            tokio::time::timeout(Duration::from_millis(10), client.request(&request)).await;
+
+        assert!(result.is_err());
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::check_status;
+    use crate::api::{completions, BuildRequest};
+    use crate::{Client, ClientError, Model};
+
+    fn response(status: u16, body: &str) -> crate::Response {
+        http::Response::builder()
+            .status(status)
+            .body(body.to_string())
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn rate_limit_error() {
+        let body = r#"{
+            "error": {
+                "message": "Rate limit reached for default-text-davinci-002",
+                "type": "requests",
+                "param": null,
+                "code": "rate_limit_exceeded"
+            }
+        }"#;
+
+        match wait!(check_status(response(429, body))) {
+            Err(ClientError::Api(error)) => {
+                assert_eq!(error.status, 429);
+                assert_eq!(error.kind, "requests");
+                assert_eq!(error.code.as_deref(), Some("rate_limit_exceeded"));
+                assert_eq!(error.param, None);
+            }
+            other => panic!("expected an api error, got {other:?}"),
+        }
+    }
+    #[test]
+    fn non_json_error() {
+        match wait!(check_status(response(502, "Bad Gateway"))) {
+            Err(ClientError::Api(error)) => {
+                assert_eq!(error.status, 502);
+                assert_eq!(error.message, "Bad Gateway");
+            }
+            other => panic!("expected an api error, got {other:?}"),
+        }
+    }
+    #[test]
+    fn request_id() {
+        let failed: crate::Response = http::Response::builder()
+            .status(500)
+            .header("x-request-id", "req-123")
+            .body(String::new())
+            .unwrap()
+            .into();
+
+        let error = wait!(check_status(failed)).unwrap_err();
+        assert_eq!(error.request_id(), Some("req-123"));
+        assert_eq!(
+            wait!(check_status(response(502, "")))
+                .unwrap_err()
+                .request_id(),
+            None
+        );
+    }
+    #[test]
+    fn success() {
+        assert!(wait!(check_status(response(200, "{}"))).is_ok());
+    }
+    #[test]
+    fn organization_header() {
+        let request = completions::Builder::default()
+            .model(Model::Ada)
+            .build()
+            .unwrap();
+
+        let client = Client::with_organization("token".to_string(), "org-123".to_string());
+        let built = request.build_request(&client).build().unwrap();
+        assert_eq!(built.headers()["OpenAI-Organization"], "org-123");
+        assert_eq!(built.headers()["Authorization"], "Bearer token");
+
+        let client = Client::new("token".to_string());
+        let built = request.build_request(&client).build().unwrap();
+        assert!(built.headers().get("OpenAI-Organization").is_none());
+    }
+    #[test]
+    fn base_url() {
+        let client = Client::builder("token".to_string())
+            .base_url("http://localhost:8080/v1/".to_string())
+            .build()
+            .unwrap();
+
+        let request = completions::Builder::default()
+            .model(Model::Ada)
+            .build()
+            .unwrap();
+        let built = request.build_request(&client).build().unwrap();
+        assert_eq!(
+            built.url().as_str(),
+            "http://localhost:8080/v1/engines/text-ada-001/completions"
+        );
+
+        let built = crate::api::files::list::Request::default()
+            .build_request(&client)
+            .build()
+            .unwrap();
+        assert_eq!(built.url().as_str(), "http://localhost:8080/v1/files");
+    }
+    /// Serves a single http response on a local port and returns the base url
+    fn serve_once(status: u16, body: &'static str) -> String {
+        serve(status, body, 1)
+    }
+    /// Serves the same http response to `connections` requests on a local port and returns the base url
+    fn serve(status: u16, body: &'static str, connections: usize) -> String {
+        serve_with(status, connections, move |_| body.to_string())
+    }
+    /// Serves `connections` requests on a local port with a body created from the request line
+    /// and returns the base url
+    fn serve_with(
+        status: u16,
+        connections: usize,
+        body: impl Fn(&str) -> String + Send + 'static,
+    ) -> String {
+        use std::io::{BufRead, BufReader, Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/v1", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming().take(connections) {
+                let mut reader = BufReader::new(stream.unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line == "\r\n" {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+                }
+                reader
+                    .by_ref()
+                    .take(content_length)
+                    .read_to_end(&mut Vec::new())
+                    .unwrap();
+                let body = body(&request_line);
+                write!(
+                    reader.get_mut(),
+                    "HTTP/1.1 {status} OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+        url
+    }
+    #[test]
+    fn request_over_http() {
+        let body = r#"{
+            "id": "cmpl-1",
+            "object": "text_completion",
+            "created": 1,
+            "model": "text-ada-001",
+            "choices": [{"text": "3", "index": 0, "logprobs": null, "finish_reason": "stop"}]
+        }"#;
+        let client = Client::builder("token".to_string())
+            .base_url(serve_once(200, body))
+            .build()
+            .unwrap();
+        let request = completions::Builder::default()
+            .model(Model::Ada)
+            .prompt(["what is 1 + 2?"])
+            .build()
+            .unwrap();
+
+        let response = wait!(client.request(&request)).unwrap();
+        assert_eq!(response.choices[0].text, "3");
+    }
+    #[test]
+    fn files_use_the_same_dispatch() {
+        let body = r#"{"object": "list", "data": []}"#;
+        let client = Client::builder("token".to_string())
+            .base_url(serve_once(200, body))
+            .build()
+            .unwrap();
+
+        let response = wait!(client.request(&crate::api::files::list::Request::default())).unwrap();
+        assert!(response.data.is_empty());
+    }
+    #[test]
+    fn batch_keeps_order() {
+        // GET /v1/files/{id} HTTP/1.1
+        let url = serve_with(200, 3, |request_line| {
+            let id = request_line.split(['/', ' ']).nth(4).unwrap();
+            format!(
+                r#"{{"id": "{id}", "object": "file", "bytes": 1, "created_at": 1, "filename": "{id}.jsonl", "purpose": "search"}}"#
+            )
+        });
+        let client = Client::builder("token".to_string())
+            .base_url(url)
+            .build()
+            .unwrap();
+        let requests = ["file-a", "file-b", "file-c"]
+            .map(|id| crate::api::files::metadata::Request::new(id.to_string()))
+            .to_vec();
+
+        let ids = wait!(client.request_batch(requests, 2))
+            .into_iter()
+            .map(|response| response.unwrap().id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, ["file-a", "file-b", "file-c"]);
+    }
+    #[test]
+    fn hooks_redact_token() {
+        use std::sync::{Arc, Mutex};
+
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let statuses = Arc::new(Mutex::new(Vec::new()));
+        let client = Client::builder("secret-token".to_string())
+            .base_url(serve_once(200, r#"{"object": "list", "data": []}"#))
+            .on_request({
+                let requests = requests.clone();
+                move |request| {
+                    requests.lock().unwrap().push(format!(
+                        "{} {} {:?} {:?}",
+                        request.method,
+                        request.url.path(),
+                        request.headers,
+                        request.body
+                    ))
+                }
+            })
+            .on_response({
+                let statuses = statuses.clone();
+                move |response| statuses.lock().unwrap().push(response.status.as_u16())
+            })
+            .build()
+            .unwrap();
+
+        wait!(client.request(&crate::api::files::list::Request::default())).unwrap();
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].starts_with("GET /v1/files"));
+        assert!(requests[0].contains("authorization"));
+        assert!(!requests[0].contains("secret-token"));
+        assert_eq!(*statuses.lock().unwrap(), [200]);
+    }
+    #[test]
+    fn on_large_body() {
+        use std::sync::{Arc, Mutex};
+
+        let sizes = Arc::new(Mutex::new(Vec::new()));
+        let client = Client::builder("token")
+            .transport(
+                crate::transport::MockTransport::new()
+                    .respond(200, "{}")
+                    .respond(200, "{}"),
+            )
+            .on_large_body(100, {
+                let sizes = sizes.clone();
+                move |request| sizes.lock().unwrap().push(request.body.unwrap().len())
+            })
+            .build()
+            .unwrap();
+        let request = |prompt: &str| {
+            completions::Builder::default()
+                .model(Model::Ada)
+                .prompt(prompt)
+                .build()
+                .unwrap()
+        };
+
+        let long = request(&"long ".repeat(50));
+        wait!(client.request_bytes(&request("short"))).unwrap();
+        wait!(client.request_bytes(&long)).unwrap();
+
+        assert_eq!(client.large_body_threshold(), Some(100));
+        assert_eq!(
+            *sizes.lock().unwrap(),
+            [crate::api::BuildRequest::build_request(&long, &client)
+                .build()
+                .unwrap()
+                .body()
+                .unwrap()
+                .as_bytes()
+                .unwrap()
+                .len()]
+        );
+        assert_eq!(Client::new("token").large_body_threshold(), None);
+    }
+    #[test]
+    fn dry_run() {
+        let client = Client::new("token".to_string());
+        let request = completions::Builder::default()
+            .model(Model::Curie)
+            .prompt("Say this is a test")
+            .max_tokens(5)
+            .build()
+            .unwrap();
+
+        let dry_run = client.dry_run(&request).unwrap();
+
+        assert_eq!(dry_run.method, reqwest::Method::POST);
+        assert_eq!(
+            dry_run.url.as_str(),
+            "https://api.openai.com/v1/engines/text-curie-001/completions"
+        );
+        assert_eq!(
+            dry_run.body,
+            Some(serde_json::json!({"prompt": ["Say this is a test"], "max_tokens": 5}))
+        );
+
+        let dry_run = client
+            .dry_run(&crate::api::files::list::Request::default())
+            .unwrap();
+        assert_eq!(dry_run.method, reqwest::Method::GET);
+        assert_eq!(dry_run.body, None);
+    }
+    #[test]
+    fn request_with_json() {
+        let body = r#"{"object": "list", "data": [], "has_more": false}"#;
+        let client = Client::builder("token".to_string())
+            .base_url(serve_once(200, body))
+            .build()
+            .unwrap();
+
+        let (response, json) =
+            wait!(client.request_with_json(&crate::api::files::list::Request::default())).unwrap();
+
+        assert!(response.data.is_empty());
+        assert_eq!(json["has_more"], false);
+    }
+    // the async stream is covered by `async_tests::timeout_cancels_request`,
+    // closing its connection needs the runtime to keep running after the drop
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn dropping_stream_closes_connection() {
+        use std::io::{BufRead, BufReader, Read, Write};
+        use std::time::Duration;
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/v1", listener.local_addr().unwrap());
+        let (closed, is_closed) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut line = String::new();
+            while line != "\r\n" {
+                line.clear();
+                reader.read_line(&mut line).unwrap();
+            }
+            let chunk = r#"data: {"id":"cmpl-1","object":"text_completion","created":1,"model":"text-ada-001","choices":[{"text":"Hello","index":0,"logprobs":null,"finish_reason":null}]}"#;
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\n\r\n{chunk}\n\n"
+            )
+            .unwrap();
+            // the client closing the connection ends the body it sent
+            let mut rest = Vec::new();
+            reader.read_to_end(&mut rest).ok();
+            closed.send(()).unwrap();
+        });
+        let client = Client::builder("token".to_string())
+            .base_url(url)
+            .build()
+            .unwrap();
+        let request = completions::Builder::default()
+            .model(Model::Ada)
+            .build()
+            .unwrap();
+
+        let mut stream = wait!(client.request_stream(&request)).unwrap();
+        assert_eq!(stream.next().unwrap().unwrap().choices[0].text, "Hello");
+        drop(stream);
+
+        is_closed.recv_timeout(Duration::from_secs(5)).unwrap();
+    }
+    #[test]
+    fn request_with_auth() {
+        let client = Client::with_organization("token".to_string(), "org-123".to_string());
+        let request = completions::Builder::default()
+            .model(Model::Ada)
+            .build()
+            .unwrap();
+
+        let built = request
+            .build_request(&client.with_token("other-token"))
+            .build()
+            .unwrap();
+        assert_eq!(built.headers()["Authorization"], "Bearer other-token");
+        assert_eq!(built.headers()["OpenAI-Organization"], "org-123");
+        assert_eq!(client.gpt_token(), "token");
+
+        let client = Client::builder("token".to_string())
+            .base_url(serve_once(200, r#"{"object": "list", "data": []}"#))
+            .build()
+            .unwrap();
+        let response =
+            wait!(client
+                .request_with_auth(&crate::api::files::list::Request::default(), "other-token"))
+            .unwrap();
+        assert!(response.data.is_empty());
+    }
+    #[test]
+    fn moderate_input() {
+        let moderation = |flagged: bool| {
+            format!(
+                r#"{{"id": "modr-1", "model": "text-moderation-004", "results": [{{"flagged": {flagged}, "categories": {{"hate": false, "violence": {flagged}}}, "category_scores": {{"hate": 0.01, "violence": 0.9}}}}]}}"#
+            )
+        };
+        let completion = r#"{"id": "cmpl-1", "object": "text_completion", "created": 1, "model": "ada", "choices": [{"text": " world", "index": 0, "logprobs": null, "finish_reason": "stop"}]}"#;
+        let request = completions::Builder::default()
+            .model(Model::Ada)
+            .prompt("hello")
+            .build()
+            .unwrap();
+
+        let client = Client::builder("token".to_string())
+            .base_url(serve_with(200, 1, move |_| moderation(true)))
+            .moderate_input(true)
+            .build()
+            .unwrap();
+        match wait!(client.request(&request)) {
+            Err(ClientError::Moderated(categories)) => assert_eq!(categories, ["violence"]),
+            other => panic!("expected a moderation error, got {other:?}"),
+        }
+
+        // POST /v1/moderations HTTP/1.1
+        let client = Client::builder("token".to_string())
+            .base_url(serve_with(200, 2, move |request_line| {
+                if request_line.contains("/moderations") {
+                    moderation(false)
+                } else {
+                    completion.to_string()
+                }
+            }))
+            .moderate_input(true)
+            .build()
+            .unwrap();
+        let response = wait!(client.request(&request)).unwrap();
+        assert_eq!(response.choices[0].text, " world");
+    }
+    #[test]
+    fn validate_key() {
+        let transport = std::sync::Arc::new(
+            crate::transport::MockTransport::new()
+                .respond(200, r#"{"object": "list", "data": []}"#)
+                .respond(401, r#"{"error": {"message": "Incorrect API key provided: sk-abc***", "type": "invalid_request_error", "param": null, "code": "invalid_api_key"}}"#)
+                .respond(500, r#"{"error": {"message": "The server had an error", "type": "server_error", "param": null, "code": null}}"#),
+        );
+        let client = Client::with_transport("sk-abc", transport.clone());
+
+        wait!(client.validate_key()).unwrap();
+        let error = wait!(client.validate_key()).unwrap_err();
+        assert!(
+            matches!(&error, ClientError::Unauthorized(error) if error.code.as_deref() == Some("invalid_api_key"))
+        );
+        assert_eq!(error.to_string(), "the api key was rejected");
+        assert_eq!(
+            std::error::Error::source(&error).unwrap().to_string(),
+            "invalid_request_error (401): Incorrect API key provided: sk-abc***"
+        );
+        assert!(matches!(
+            wait!(client.validate_key()),
+            Err(ClientError::Api(_))
+        ));
+        assert_eq!(transport.requests()[0].url.path(), "/v1/engines");
+    }
+    #[test]
+    fn retries() {
+        let server_error = r#"{"error": {"message": "The server is overloaded", "type": "server_error", "param": null, "code": null}}"#;
+        let transport = std::sync::Arc::new(
+            crate::transport::MockTransport::new()
+                .respond(503, server_error)
+                .respond(503, server_error)
+                .respond(200, r#"{"object": "list", "data": []}"#)
+                .respond(503, server_error)
+                .respond(503, server_error)
+                .respond(429, r#"{"error": {"message": "You exceeded your current quota", "type": "insufficient_quota", "param": null, "code": "insufficient_quota"}}"#),
+        );
+        let client = Client::builder("token")
+            .transport(transport.clone())
+            .retry_policy(crate::RetryPolicy::new(
+                2,
+                std::time::Duration::from_millis(1),
+            ))
+            .build()
+            .unwrap();
+        let request = crate::api::files::list::Request::default();
+
+        assert!(wait!(client.request(&request)).is_ok());
+        assert_eq!(transport.requests().len(), 3);
+
+        let client = Client::builder("token")
+            .transport(transport.clone())
+            .retry_policy(crate::RetryPolicy::new(
+                1,
+                std::time::Duration::from_millis(1),
+            ))
+            .build()
+            .unwrap();
+        assert!(matches!(
+            wait!(client.request(&request)),
+            Err(ClientError::Api(error)) if error.status == 503
+        ));
+        assert_eq!(transport.requests().len(), 5);
+        // an exhausted quota isn't retried
+        assert!(matches!(
+            wait!(client.request(&request)),
+            Err(ClientError::Api(error)) if error.code.as_deref() == Some("insufficient_quota")
+        ));
+        assert_eq!(transport.requests().len(), 6);
+    }
+    #[test]
+    fn request_bytes() {
+        let png = vec![0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a, 0xff];
+        let client = Client::with_transport(
+            "token",
+            crate::transport::MockTransport::new()
+                .respond(200, png.clone())
+                .respond(404, r#"{"error": {"message": "No such File object: file-1", "type": "invalid_request_error", "param": "id", "code": null}}"#),
+        );
+        let request = crate::api::files::content::Request::new("file-1".to_string());
+
+        assert_eq!(wait!(client.request_bytes(&request)).unwrap().as_ref(), png);
+        assert!(matches!(
+            wait!(client.request_bytes(&request)),
+            Err(ClientError::Api(error)) if error.message == "No such File object: file-1"
+        ));
+    }
+    #[test]
+    fn request_stream_collect() {
+        let chunk = |text: &str, index: usize| {
+            format!(
+                r#"data: {{"id":"cmpl-1","object":"text_completion","created":1,"model":"text-ada-001","choices":[{{"text":"{text}","index":{index},"logprobs":null,"finish_reason":null}}]}}"#
+            )
+        };
+        let body = [
+            chunk("Hello", 0),
+            chunk("Bye", 1),
+            chunk(" world", 0),
+            "data: [DONE]".to_string(),
+        ]
+        .join("\n\n");
+        let client = Client::with_transport(
+            "token".to_string(),
+            crate::transport::MockTransport::new()
+                .respond(200, body)
+                .respond(200, "data: {}\n\n"),
+        );
+        let request = completions::Builder::default()
+            .model(Model::Ada)
+            .prompt("hello")
+            .n(2)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            wait!(client.request_stream_collect(&request)).unwrap(),
+            "Hello world"
+        );
+        assert!(matches!(
+            wait!(client.request_stream_collect(&request)),
+            Err(ClientError::Deserialize(_))
+        ));
+    }
+    #[test]
+    fn clones_share_settings() {
+        fn shareable<T: Clone + Send + Sync + 'static>() {}
+        shareable::<Client>();
+
+        let client = Client::builder("token")
+            .base_url(serve(200, r#"{"object": "list", "data": []}"#, 2))
+            .build()
+            .unwrap();
+        let clone = client.clone();
+
+        assert_eq!(clone.base_url(), client.base_url());
+        std::thread::spawn(move || {
+            wait!(clone.request(&crate::api::files::list::Request::default())).unwrap()
+        })
+        .join()
+        .unwrap();
+        wait!(client.request(&crate::api::files::list::Request::default())).unwrap();
+    }
+    #[test]
+    fn with_reqwest() {
+        use std::io::{BufRead, BufReader, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/v1", listener.local_addr().unwrap());
+        let (sent, user_agent) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut line = String::new();
+            while line != "\r\n" {
+                line.clear();
+                reader.read_line(&mut line).unwrap();
+                if let Some(value) = line.strip_prefix("user-agent: ") {
+                    sent.send(value.trim().to_string()).unwrap();
+                }
+            }
+            let body = r#"{"object": "list", "data": []}"#;
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 200 OK\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+        });
+        let reqwest_client = super::RequestClient::builder()
+            .user_agent("acme/1.0")
+            .build()
+            .unwrap();
+        let client = Client {
+            base_url: url,
+            ..Client::with_reqwest("token", reqwest_client)
+        };
+
+        wait!(client.request(&crate::api::files::list::Request::default())).unwrap();
+        assert_eq!(user_agent.recv().unwrap(), "acme/1.0");
+    }
+    #[test]
+    fn from_env() {
+        // the variable itself isn't touched, tests run in parallel
+        let error = Client::from_api_key(None).err().unwrap();
+        assert!(matches!(error, ClientError::MissingApiKey));
+        assert_eq!(
+            error.to_string(),
+            "the OPENAI_API_KEY environment variable is not set"
+        );
+
+        let client = Client::from_api_key(Some("sk-test".to_string())).unwrap();
+        assert_eq!(client.gpt_token(), "sk-test");
+    }
+}
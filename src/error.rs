@@ -0,0 +1,243 @@
+use std::fmt;
+
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+
+/// An error returned by the OpenAi api
+///
+/// # Example
+/// ```ignore
+/// match request.request(&client).await {
+///     Err(ClientError::Api(error)) => println!("{}: {}", error.status, error.message),
+///     _ => (),
+/// }
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiError {
+    /// The http status code of the response
+    #[serde(skip)]
+    pub status: u16,
+    /// A human readable description of the error
+    pub message: String,
+    /// The category of the error, e.g. `invalid_request_error`
+    #[serde(rename = "type", default)]
+    pub kind: String,
+    /// A machine readable error code
+    pub code: Option<String>,
+    /// The request parameter that caused the error
+    pub param: Option<String>,
+    /// The id the api assigned to the request, sent in the `x-request-id` header.
+    /// Include it when contacting OpenAI support about an error.
+    // boxed to keep `ClientError` small
+    #[serde(skip)]
+    pub request_id: Option<Box<str>>,
+}
+#[derive(Deserialize)]
+struct ErrorBody {
+    error: ApiError,
+}
+impl ApiError {
+    /// Parses an error response body.
+    /// Bodies that aren't in the OpenAi error format (e.g. from a proxy) are kept as the message.
+    pub(crate) fn from_body(status: StatusCode, body: &str) -> Self {
+        match serde_json::from_str::<ErrorBody>(body) {
+            Ok(ErrorBody { error }) => ApiError {
+                status: status.as_u16(),
+                ..error
+            },
+            Err(_) => ApiError {
+                status: status.as_u16(),
+                message: if body.is_empty() {
+                    status.canonical_reason().unwrap_or_default().to_string()
+                } else {
+                    body.to_string()
+                },
+                ..Default::default()
+            },
+        }
+    }
+    /// Whether the request was rejected because of the rate limit and can be retried later
+    ///
+    /// An exhausted quota (`insufficient_quota`) is also sent with status 429, but isn't a rate limit.
+    #[must_use]
+    pub fn is_rate_limit(&self) -> bool {
+        self.code.as_deref() == Some("rate_limit_exceeded")
+            || (self.status == 429 && self.code.as_deref() != Some("insufficient_quota"))
+    }
+    /// Whether the request itself was invalid, e.g. an unknown model or an out of range parameter,
+    /// see [`param`](Self::param) for the parameter that caused it
+    #[must_use]
+    pub fn is_invalid_request(&self) -> bool {
+        self.kind == "invalid_request_error"
+    }
+    /// Whether the api token is invalid or missing
+    #[must_use]
+    pub fn is_authentication(&self) -> bool {
+        self.status == 401 || self.kind == "authentication_error"
+    }
+}
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}): {}", self.kind, self.status, self.message)
+    }
+}
+
+/// An error that occurred while sending a request
+#[derive(Debug)]
+pub enum ClientError {
+    /// The api responded with a non success status
+    Api(ApiError),
+    /// The request couldn't be sent or the response couldn't be read
+    Http(reqwest::Error),
+    /// The response couldn't be deserialized
+    Deserialize(serde_json::Error),
+    /// Reading a streamed response failed
+    Io(std::io::Error),
+    /// The input was flagged by the moderations endpoint and wasn't sent,
+    /// contains the flagged categories, see [`ClientBuilder::moderate_input`](crate::ClientBuilder::moderate_input)
+    Moderated(Vec<String>),
+    /// The `OPENAI_API_KEY` environment variable isn't set, returned by [`Client::from_env`](crate::Client::from_env)
+    MissingApiKey,
+    /// The api rejected the api token, returned by [`Client::validate_key`](crate::Client::validate_key)
+    Unauthorized(ApiError),
+}
+impl ClientError {
+    /// The id of the failed request, if the api responded with an error that contained one
+    /// # Example
+    /// ```ignore
+    /// if let Err(error) = request.request(&client).await {
+    ///     eprintln!("{error} (request id: {:?})", error.request_id());
+    /// }
+    /// ```
+    #[must_use]
+    pub fn request_id(&self) -> Option<&str> {
+        match self {
+            ClientError::Api(error) | ClientError::Unauthorized(error) => {
+                error.request_id.as_deref()
+            }
+            _ => None,
+        }
+    }
+}
+/// The inner error of a variant isn't part of the message, it's the [`source`](std::error::Error::source) instead
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Api(_) => write!(f, "api error"),
+            ClientError::Http(_) => write!(f, "http error"),
+            ClientError::Deserialize(_) => write!(f, "deserialize error"),
+            ClientError::Io(_) => write!(f, "io error"),
+            ClientError::Moderated(categories) => {
+                write!(f, "input flagged by moderation: {}", categories.join(", "))
+            }
+            ClientError::MissingApiKey => {
+                write!(f, "the OPENAI_API_KEY environment variable is not set")
+            }
+            ClientError::Unauthorized(_) => write!(f, "the api key was rejected"),
+        }
+    }
+}
+impl std::error::Error for ClientError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ClientError::Api(error) | ClientError::Unauthorized(error) => Some(error),
+            ClientError::Http(error) => Some(error),
+            ClientError::Deserialize(error) => Some(error),
+            ClientError::Io(error) => Some(error),
+            ClientError::Moderated(_) | ClientError::MissingApiKey => None,
+        }
+    }
+}
+impl std::error::Error for ApiError {}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(error: reqwest::Error) -> Self {
+        ClientError::Http(error)
+    }
+}
+impl From<serde_json::Error> for ClientError {
+    fn from(error: serde_json::Error) -> Self {
+        ClientError::Deserialize(error)
+    }
+}
+impl From<std::io::Error> for ClientError {
+    fn from(error: std::io::Error) -> Self {
+        ClientError::Io(error)
+    }
+}
+impl From<ApiError> for ClientError {
+    fn from(error: ApiError) -> Self {
+        ClientError::Api(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use reqwest::StatusCode;
+
+    use super::{ApiError, ClientError};
+
+    fn parse(json: &str) -> Result<serde_json::Value, ClientError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    #[test]
+    fn classifiers() {
+        let error =
+            |status, body: &str| ApiError::from_body(StatusCode::from_u16(status).unwrap(), body);
+
+        let rate_limit = error(
+            429,
+            r#"{"error": {"message": "Rate limit reached for requests", "type": "requests", "param": null, "code": "rate_limit_exceeded"}}"#,
+        );
+        assert!(rate_limit.is_rate_limit());
+        assert!(!rate_limit.is_invalid_request());
+
+        let quota = error(
+            429,
+            r#"{"error": {"message": "You exceeded your current quota", "type": "insufficient_quota", "param": null, "code": "insufficient_quota"}}"#,
+        );
+        assert!(!quota.is_rate_limit());
+
+        let invalid = error(
+            400,
+            r#"{"error": {"message": "0.5 is not of type 'integer' - 'n'", "type": "invalid_request_error", "param": "n", "code": null}}"#,
+        );
+        assert!(invalid.is_invalid_request());
+        assert_eq!(invalid.param.as_deref(), Some("n"));
+        assert_eq!(invalid.code, None);
+
+        let unauthorized = error(
+            401,
+            r#"{"error": {"message": "Incorrect API key provided", "type": "invalid_request_error", "param": null, "code": "invalid_api_key"}}"#,
+        );
+        assert!(unauthorized.is_authentication());
+        assert_eq!(unauthorized.code.as_deref(), Some("invalid_api_key"));
+        assert!(error(429, "").is_rate_limit());
+    }
+    #[test]
+    fn boxed_errors() {
+        fn boxed(json: &str) -> Result<serde_json::Value, Box<dyn Error + Send + Sync>> {
+            Ok(parse(json)?)
+        }
+
+        let error = boxed("{").unwrap_err();
+        assert_eq!(error.to_string(), "deserialize error");
+        assert!(error.source().unwrap().is::<serde_json::Error>());
+
+        let error = ClientError::from(ApiError {
+            status: 429,
+            message: "Rate limit reached".to_string(),
+            kind: "requests".to_string(),
+            ..Default::default()
+        });
+        assert_eq!(error.to_string(), "api error");
+        assert_eq!(
+            error.source().unwrap().to_string(),
+            "requests (429): Rate limit reached"
+        );
+        assert!(ClientError::MissingApiKey.source().is_none());
+    }
+}
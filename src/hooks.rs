@@ -0,0 +1,45 @@
+//! Callbacks to observe the requests sent by a [`Client`](crate::Client), e.g. for logging
+use std::sync::Arc;
+
+use reqwest::{
+    header::{HeaderMap, HeaderValue, AUTHORIZATION},
+    Method, StatusCode, Url,
+};
+
+/// A request that is about to be sent, passed to [`ClientBuilder::on_request`](crate::ClientBuilder::on_request)
+#[derive(Debug)]
+pub struct RequestLog<'a> {
+    /// The http method of the request
+    pub method: &'a Method,
+    /// The url of the request
+    pub url: &'a Url,
+    /// The headers of the request, with the `Authorization` header redacted
+    pub headers: HeaderMap,
+    /// The body of the request, `None` for requests without a body or with a streamed body like file uploads
+    pub body: Option<&'a [u8]>,
+}
+/// A response that was received, passed to [`ClientBuilder::on_response`](crate::ClientBuilder::on_response)
+///
+/// The body isn't included, because it may be streamed.
+#[derive(Debug)]
+pub struct ResponseLog<'a> {
+    /// The http status of the response
+    pub status: StatusCode,
+    /// The url of the response
+    pub url: &'a Url,
+    /// The headers of the response
+    pub headers: &'a HeaderMap,
+}
+
+pub(crate) type RequestHook = Arc<dyn Fn(&RequestLog<'_>) + Send + Sync>;
+pub(crate) type ResponseHook = Arc<dyn Fn(&ResponseLog<'_>) + Send + Sync>;
+
+/// Copies the headers, replacing the api token so it never ends up in a log
+pub(crate) fn redact(headers: &HeaderMap) -> HeaderMap {
+    let mut headers = headers.clone();
+    if let Some(value) = headers.get_mut(AUTHORIZATION) {
+        *value = HeaderValue::from_static("[redacted]");
+        value.set_sensitive(true);
+    }
+    headers
+}
@@ -1,7 +1,6 @@
 use std::ops::Deref;
 
 use serde::{Deserialize, Serialize};
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 /// Convenience type to allow using `&[&str]` instead of `Vec<String>` on the builder methods.
 ///
 /// # Example
@@ -9,22 +8,93 @@ use serde::{Deserialize, Serialize};
 /// ```ignore
 /// &["some", "thing"]
 /// ```
-/// instead of: 
+/// instead of:
 /// ```ignore
 /// vec!["some".into(), "thing".into()]
 /// ```
+/// # Conversions
+/// [`IntoVec<String>`] can be created from a single `&str` or `String`,
+/// or from slices, arrays and vectors of either.
+/// Any `IntoVec<T>` can also be created from a `Vec<T>`, `[T; N]`, `&[T]` or by collecting an iterator.
+/// ```
+/// # use gpt3_rs::IntoVec;
+/// let single: IntoVec<String> = "some".into();
+/// let slice: IntoVec<String> = (&["some", "thing"][..]).into();
+/// let array: IntoVec<String> = (&["some", "thing"]).into();
+/// let vec: IntoVec<String> = vec!["some".to_string(), "thing".to_string()].into();
+///
+/// assert_eq!(&*single, ["some"]);
+/// assert_eq!(slice, array);
+/// assert_eq!(array, vec);
+///
+/// let collected: IntoVec<String> = (1..=2).map(|n| format!("document {n}")).collect();
+/// assert_eq!(&*collected, ["document 1", "document 2"]);
+/// ```
+/// [`IntoVec<Vec<String>>`] can be created from nested slices, arrays and vectors.
+/// ```
+/// # use gpt3_rs::IntoVec;
+/// let nested: IntoVec<Vec<String>> = (&[&["A happy moment", "Positive"], &["I am sad.", "Negative"]]).into();
+/// let vec: IntoVec<Vec<String>> = vec![vec!["A happy moment", "Positive"], vec!["I am sad.", "Negative"]].into();
+///
+/// assert_eq!(nested, vec);
+/// assert_eq!(nested[1], ["I am sad.", "Negative"]);
+/// ```
+/// # Deserializing
+/// Like the api, a single value is accepted in place of a list.
+/// ```
+/// # use gpt3_rs::IntoVec;
+/// let single: IntoVec<String> = serde_json::from_str(r#""some""#).unwrap();
+///
+/// assert_eq!(&*single, ["some"]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct IntoVec<T>(Vec<T>);
 
-impl From<Vec<String>> for IntoVec<String> {
-    fn from(from: Vec<String>) -> Self {
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for IntoVec<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum OneOrMany<T> {
+            Many(Vec<T>),
+            One(T),
+        }
+        Ok(match OneOrMany::deserialize(deserializer)? {
+            OneOrMany::Many(vec) => IntoVec(vec),
+            OneOrMany::One(value) => IntoVec(vec![value]),
+        })
+    }
+}
+
+impl From<&str> for IntoVec<String> {
+    fn from(from: &str) -> Self {
+        IntoVec(vec![from.to_owned()])
+    }
+}
+impl From<String> for IntoVec<String> {
+    fn from(from: String) -> Self {
+        IntoVec(vec![from])
+    }
+}
+impl<T> From<Vec<T>> for IntoVec<T> {
+    fn from(from: Vec<T>) -> Self {
         IntoVec(from)
     }
 }
-impl From<&[String]> for IntoVec<String> {
-    fn from(from: &[String]) -> Self {
+impl<T: Clone> From<&[T]> for IntoVec<T> {
+    fn from(from: &[T]) -> Self {
         IntoVec(from.to_vec())
     }
 }
+impl<T, const N: usize> From<[T; N]> for IntoVec<T> {
+    fn from(from: [T; N]) -> Self {
+        IntoVec(from.into())
+    }
+}
+impl<T> FromIterator<T> for IntoVec<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        IntoVec(iter.into_iter().collect())
+    }
+}
 impl From<&[&str]> for IntoVec<String> {
     fn from(from: &[&str]) -> Self {
         IntoVec(from.iter().cloned().map(ToOwned::to_owned).collect())
@@ -40,6 +110,11 @@ impl<const N: usize> From<&[&str; N]> for IntoVec<String> {
         IntoVec(from.iter().cloned().map(ToOwned::to_owned).collect())
     }
 }
+impl<const N: usize> From<[&str; N]> for IntoVec<String> {
+    fn from(from: [&str; N]) -> Self {
+        IntoVec(from.iter().cloned().map(ToOwned::to_owned).collect())
+    }
+}
 impl From<Vec<&str>> for IntoVec<String> {
     fn from(from: Vec<&str>) -> Self {
         IntoVec(from.into_iter().map(ToOwned::to_owned).collect())
@@ -15,7 +15,7 @@
 //!
 //!     let request = completions::Builder::default()
 //!         .model(Model::Babbage)
-//!         .prompt("what is 1 + 2?".into())
+//!         .prompt("what is 1 + 2?")
 //!         .build()
 //!         .unwrap();
 //!
@@ -25,11 +25,35 @@
 //!     println!("{answer}");
 //! }
 //! ```
+//! # Blocking
+//! With the `blocking` feature, requests are sent with `reqwest::blocking`
+//! and don't need an async runtime.
+//! ```ignore
+//! let client = Client::new(token);
+//! let response = client.request(&request).unwrap();
+//! ```
+//! # Tls
+//! The tls backend of reqwest is chosen with the `rustls-tls` (default) and `native-tls` features.
+//! Only one of them should be enabled, e.g. for static musl builds keep the default,
+//! or use the system's openssl with
+//! ```toml
+//! gpt3_rs = { version = "0.3", default-features = false, features = ["native-tls"] }
+//! ```
 #[cfg(not(feature = "blocking"))]
 pub(crate) type RequestBuilder = reqwest::RequestBuilder;
 #[cfg(feature = "blocking")]
 pub(crate) type RequestBuilder = reqwest::blocking::RequestBuilder;
 
+#[cfg(not(feature = "blocking"))]
+pub(crate) type Response = reqwest::Response;
+#[cfg(feature = "blocking")]
+pub(crate) type Response = reqwest::blocking::Response;
+
+#[cfg(not(feature = "blocking"))]
+pub(crate) type HttpRequest = reqwest::Request;
+#[cfg(feature = "blocking")]
+pub(crate) type HttpRequest = reqwest::blocking::Request;
+
 #[cfg(feature = "blocking")]
 pub(crate) type Part = reqwest::blocking::multipart::Part;
 #[cfg(not(feature = "blocking"))]
@@ -40,19 +64,80 @@ pub(crate) type Form = reqwest::blocking::multipart::Form;
 #[cfg(not(feature = "blocking"))]
 pub(crate) type Form = reqwest::multipart::Form;
 
+const OPENAI_URL: &str = "https://api.openai.com/v1";
+
+/// Waits for a call of the client in the tests, so the same tests cover the async and the blocking client
+#[cfg(test)]
+macro_rules! wait {
+    ($call:expr) => {{
+        #[cfg(not(feature = "blocking"))]
+        let output = crate::test_util::block_on($call);
+        #[cfg(feature = "blocking")]
+        let output = $call;
+        output
+    }};
+}
+#[cfg(test)]
+mod test_util;
+
 pub mod api;
 mod client;
+mod error;
+pub mod hooks;
 mod into_vec;
 mod model;
+mod rate_limit;
+mod retry;
+mod stream;
+mod template;
+#[cfg(feature = "tokenizer")]
+pub mod tokenizer;
+pub mod transport;
 pub use client::Client;
-pub use model::Model;
+pub use client::ClientBuilder;
+pub use client::DryRun;
 pub use client::Request;
+pub use client::StreamRequest;
+pub use error::{ApiError, ClientError};
+pub use into_vec::IntoVec;
+pub use model::{Model, ParseModelError};
+pub use rate_limit::RateLimitInfo;
+pub use retry::RetryPolicy;
+pub use stream::ChunkStream;
+pub use template::{Template, TemplateError};
 
+/// Everything needed to build and send requests
+///
+/// # Example
+/// ```no_run
+/// use gpt3_rs::prelude::*;
+///
+/// # #[cfg(not(feature = "blocking"))]
+/// async fn answer() -> Result<String, Box<dyn std::error::Error>> {
+///     let client = Client::from_env()?;
+///     let request = completions::Builder::default()
+///         .model(Model::Curie)
+///         .prompt("what is 1 + 2?")
+///         .build()?;
+///
+///     let response = request.request(&client).await?;
+///     Ok(response.choices[0].text.clone())
+/// }
+/// ```
 pub mod prelude {
     pub use crate::api::*;
     pub use crate::client::Client;
-    pub use crate::model::Model;
+    pub use crate::client::ClientBuilder;
+    pub use crate::client::DryRun;
     pub use crate::client::Request;
+    pub use crate::client::StreamRequest;
+    pub use crate::error::{ApiError, ClientError};
+    pub use crate::into_vec::IntoVec;
+    pub use crate::model::{Model, ParseModelError};
+    pub use crate::rate_limit::RateLimitInfo;
+    pub use crate::retry::RetryPolicy;
+    pub use crate::stream::ChunkStream;
+    pub use crate::template::{Template, TemplateError};
 }
 
 #[cfg(test)]
@@ -72,14 +157,14 @@ mod tests {
             .model(Model::Ada)
             .search_model(Model::Ada)
             .question("which puppy is happy?")
-            .documents(&["Puppy A is happy", "Puppy B is sad."])
+            .documents(["Puppy A is happy", "Puppy B is sad."])
             .examples_context("In 2017, U.S. life expectancy was 78.6 years.")
             .examples(&[&[
                 "What is human life expectancy in the United States?",
                 "78 years.",
             ]])
             .max_tokens(5)
-            .stop(&["\n", "<|endoftext|>"])
+            .stop(["\n", "<|endoftext|>"])
             .build()
             .unwrap();
 
@@ -101,7 +186,7 @@ mod tests {
                 &["I am sad.", "Negative"],
                 &["I am feeling awesome", "Positive"],
             ])
-            .labels(&["Positive", "Negative", "Neutral"])
+            .labels(["Positive", "Negative", "Neutral"])
             .build()
             .unwrap();
 
@@ -116,12 +201,12 @@ mod tests {
 
         let request = completions::Builder::default()
             .model(Model::Ada)
-            .prompt(&["Say this is a test"])
+            .prompt(["Say this is a test"])
             .max_tokens(5)
             .temperature(1.0)
             .top_p(1.0)
             .n(1)
-            .stop(&["\n"])
+            .stop(["\n"])
             .build()
             .unwrap();
 
@@ -152,7 +237,7 @@ mod tests {
 
         let request = searches::Builder::default()
             .model(Model::Ada)
-            .documents(&["White house", "hospital", "school"])
+            .documents(["White house", "hospital", "school"])
             .query("the president")
             .build()
             .unwrap();
@@ -203,4 +288,3 @@ mod tests {
         println!("{:#?}", deleted);
     }
 }
-const OPENAI_URL: &str = "https://api.openai.com/v1";
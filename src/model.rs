@@ -1,9 +1,11 @@
-use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
 
-use crate::OPENAI_URL;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
+/// A model / engine of the api
+///
+/// Defaults to [`Model::Davinci`], the most capable GPT-3 model.
+#[derive(Default, Debug, Clone, PartialEq)]
 pub enum Model {
     /// # Good at
     ///  **Parsing text**, **simple classification**, **address correction**, **keywords**
@@ -39,22 +41,237 @@ pub enum Model {
     /// Another area where Davinci shines is in understanding the intent of text.
     /// Davinci is quite good at solving many kinds of logic problems and explaining the motives of characters.
     /// Davinci has been able to solve some of the most challenging AI problems involving cause and effect.
+    #[default]
     Davinci,
+    /// # Good at
+    /// **Complex intent**, **cause and effect**, **longer output**
+    /// # Description
+    /// Can do any language task with better quality, longer output, and consistent instruction-following than the curie, babbage, or ada models.
+    /// Also supports inserting completions within text.
+    Davinci003,
+    /// # Good at
+    /// **Code completion**, **translating natural language to code**
+    /// # Description
+    /// Most capable Codex model. Particularly good at translating natural language to code.
+    /// In addition to completing code, also supports inserting completions within code.
+    CodeDavinci002,
+    /// # Good at
+    /// **Semantic search**, **clustering**, **recommendations**
+    /// # Description
+    /// The second generation embedding model, replacing the 16 first generation embedding models.
+    /// Only usable with the [`embeddings`](crate::api::embeddings) endpoint.
+    TextEmbeddingAda002,
+    /// # Description
+    /// Any other model, identified by its id.
+    ///
+    /// This is used for fine-tuned models like `davinci:ft-acme-2023-01-01`,
+    /// the id is sent as is and used as the engine for engine scoped endpoints.
+    Custom(String),
 }
 
 impl Model {
-    pub(crate) fn url(&self, action: &str) -> String {
-        (match self {
-            Model::Ada => format!("{OPENAI_URL}/engines/text-ada-001"),
-            Model::Babbage => format!("{OPENAI_URL}/engines/text-babbage-001"),
-            Model::Curie => format!("{OPENAI_URL}/engines/text-curie-001"),
-            Model::Davinci => format!("{OPENAI_URL}/engines/text-davinci-002"),
-        }) + action
-    }
-    pub(crate) fn edit_url(&self, action: &str) -> String {
-        (match self {
-            Model::Davinci => format!("{OPENAI_URL}/engines/text-davinci-edit-001"),
-            _ => panic!("editing only supports davinci")
-        }) + action
+    /// Creates a model from its id, falling back to [`Model::Custom`] for unknown ids
+    /// # Example
+    /// ```ignore
+    /// assert_eq!(Model::from_id("curie"), Model::Curie);
+    /// assert_eq!(Model::from_id("curie:ft-acme"), Model::Custom("curie:ft-acme".to_string()));
+    /// ```
+    pub fn from_id(id: &str) -> Self {
+        match id {
+            "ada" => Model::Ada,
+            "babbage" => Model::Babbage,
+            "curie" => Model::Curie,
+            "davinci" => Model::Davinci,
+            "text-davinci-003" => Model::Davinci003,
+            "code-davinci-002" => Model::CodeDavinci002,
+            "text-embedding-ada-002" => Model::TextEmbeddingAda002,
+            id => Model::Custom(id.to_string()),
+        }
+    }
+    /// The id of the model as it is sent to the api
+    #[must_use]
+    pub fn id(&self) -> &str {
+        match self {
+            Model::Ada => "ada",
+            Model::Babbage => "babbage",
+            Model::Curie => "curie",
+            Model::Davinci => "davinci",
+            Model::Davinci003 => "text-davinci-003",
+            Model::CodeDavinci002 => "code-davinci-002",
+            Model::TextEmbeddingAda002 => "text-embedding-ada-002",
+            Model::Custom(id) => id,
+        }
+    }
+    /// The maximum number of tokens of a request, the prompt and the completion combined
+    ///
+    /// Fine-tuned [`Custom`](Model::Custom) models are assumed to have the context of the original GPT-3 models.
+    /// # Example
+    /// ```
+    /// # use gpt3_rs::Model;
+    /// let prompt_tokens = 1000;
+    /// let max_tokens = Model::Davinci003.context_window() - prompt_tokens;
+    ///
+    /// assert_eq!(max_tokens, 3097);
+    /// ```
+    #[must_use]
+    pub fn context_window(&self) -> usize {
+        match self {
+            Model::Ada | Model::Babbage | Model::Curie | Model::Custom(_) => 2049,
+            Model::Davinci | Model::Davinci003 => 4097,
+            Model::CodeDavinci002 => 8001,
+            Model::TextEmbeddingAda002 => 8191,
+        }
+    }
+    /// The maximum number of tokens the model can generate, if the prompt is a single token
+    ///
+    /// Embedding models don't generate text, so this is 0 for them.
+    #[must_use]
+    pub fn max_output_tokens(&self) -> usize {
+        match self {
+            Model::TextEmbeddingAda002 => 0,
+            model => model.context_window() - 1,
+        }
+    }
+    /// The id of the engine used for engine scoped endpoints
+    pub(crate) fn engine(&self) -> &str {
+        match self {
+            Model::Ada => "text-ada-001",
+            Model::Babbage => "text-babbage-001",
+            Model::Curie => "text-curie-001",
+            Model::Davinci => "text-davinci-002",
+            Model::Davinci003 => "text-davinci-003",
+            Model::CodeDavinci002 => "code-davinci-002",
+            Model::TextEmbeddingAda002 => "text-embedding-ada-002",
+            Model::Custom(id) => id,
+        }
+    }
+    pub(crate) fn url(&self, base_url: &str, action: &str) -> String {
+        format!("{base_url}/engines/{}{action}", self.engine())
+    }
+    /// The id of the model used for the edits endpoint
+    pub(crate) fn edit_id(&self) -> Option<&str> {
+        match self {
+            Model::Davinci => Some("text-davinci-edit-001"),
+            Model::CodeDavinci002 => Some("code-davinci-edit-001"),
+            Model::Custom(id) => Some(id),
+            _ => None,
+        }
+    }
+    pub(crate) fn serialize_edit<S: Serializer>(
+        model: &Model,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match model.edit_id() {
+            Some(id) => serializer.serialize_str(id),
+            None => Err(serde::ser::Error::custom(UNSUPPORTED_EDIT_MODEL)),
+        }
+    }
+}
+
+impl fmt::Display for Model {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.id())
+    }
+}
+/// Parses the id of a known model, ignoring case
+///
+/// Unlike [`Model::from_id`], unknown ids are an error instead of a [`Model::Custom`],
+/// so typos in e.g. command line arguments are caught.
+/// # Example
+/// ```
+/// # use gpt3_rs::Model;
+/// assert_eq!("Curie".parse::<Model>(), Ok(Model::Curie));
+/// assert!("gpt-5".parse::<Model>().is_err());
+/// ```
+impl FromStr for Model {
+    type Err = ParseModelError;
+
+    fn from_str(id: &str) -> Result<Self, Self::Err> {
+        match Model::from_id(&id.to_ascii_lowercase()) {
+            Model::Custom(_) => Err(ParseModelError(id.to_string())),
+            model => Ok(model),
+        }
+    }
+}
+/// The error returned when parsing an unknown [`Model`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseModelError(String);
+impl fmt::Display for ParseModelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown model `{}`", self.0)
+    }
+}
+impl std::error::Error for ParseModelError {}
+
+/// The error for models without an [`edit_id`](Model::edit_id)
+pub(crate) const UNSUPPORTED_EDIT_MODEL: &str =
+    "editing only supports davinci, code davinci and custom models";
+
+impl Serialize for Model {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.id())
+    }
+}
+impl<'de> Deserialize<'de> for Model {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let id = String::deserialize(deserializer)?;
+        Ok(Model::from_id(&id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Model;
+    use crate::OPENAI_URL;
+
+    #[test]
+    fn custom_round_trip() {
+        let model = Model::Custom("davinci:ft-acme-2023-01-01".to_string());
+        let json = serde_json::to_string(&model).unwrap();
+
+        assert_eq!(json, "\"davinci:ft-acme-2023-01-01\"");
+        assert_eq!(serde_json::from_str::<Model>(&json).unwrap(), model);
+        assert_eq!(
+            model.url(OPENAI_URL, "/completions"),
+            "https://api.openai.com/v1/engines/davinci:ft-acme-2023-01-01/completions"
+        );
+    }
+    #[test]
+    fn display_and_parse() {
+        assert_eq!(Model::Davinci003.to_string(), "text-davinci-003");
+        assert_eq!("DAVINCI".parse::<Model>().unwrap(), Model::Davinci);
+        assert_eq!(
+            "text-davinci-003".parse::<Model>().unwrap(),
+            Model::Davinci003
+        );
+        assert_eq!(
+            "curie:ft-acme".parse::<Model>().unwrap_err().to_string(),
+            "unknown model `curie:ft-acme`"
+        );
+        for model in [Model::Ada, Model::Babbage, Model::Curie, Model::Davinci] {
+            assert_eq!(model.to_string().parse::<Model>().unwrap(), model);
+        }
+    }
+    #[test]
+    fn token_limits() {
+        assert_eq!(Model::Curie.context_window(), 2049);
+        assert_eq!(Model::Davinci003.max_output_tokens(), 4096);
+        assert_eq!(Model::TextEmbeddingAda002.context_window(), 8191);
+        assert_eq!(Model::TextEmbeddingAda002.max_output_tokens(), 0);
+        assert_eq!(
+            Model::Custom("curie:ft-acme".to_string()).context_window(),
+            2049
+        );
+    }
+    #[test]
+    fn default() {
+        assert_eq!(Model::default(), Model::Davinci);
+    }
+    #[test]
+    fn known_round_trip() {
+        let json = serde_json::to_string(&Model::Curie).unwrap();
+
+        assert_eq!(json, "\"curie\"");
+        assert_eq!(serde_json::from_str::<Model>(&json).unwrap(), Model::Curie);
     }
 }
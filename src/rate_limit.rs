@@ -0,0 +1,117 @@
+//! Rate limit information sent by the api in the response headers
+use std::time::Duration;
+
+use reqwest::header::HeaderMap;
+
+/// The rate limits of the account, parsed from the `x-ratelimit-*` response headers
+///
+/// Fields are `None` if the api didn't send the corresponding header.
+///
+/// Returned by [`Client::request_with_headers`](crate::Client::request_with_headers)
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitInfo {
+    /// The maximum number of requests allowed before the limit is exhausted
+    pub limit_requests: Option<u64>,
+    /// The maximum number of tokens allowed before the limit is exhausted
+    pub limit_tokens: Option<u64>,
+    /// The remaining number of requests before the limit is exhausted
+    pub remaining_requests: Option<u64>,
+    /// The remaining number of tokens before the limit is exhausted
+    pub remaining_tokens: Option<u64>,
+    /// The time until the request limit resets to its initial state
+    pub reset_requests: Option<Duration>,
+    /// The time until the token limit resets to its initial state
+    pub reset_tokens: Option<Duration>,
+}
+
+impl RateLimitInfo {
+    /// Parses the rate limit headers of a response
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+        let count = |name: &str| header(name).and_then(|value| value.parse().ok());
+        let reset = |name: &str| header(name).and_then(parse_duration);
+
+        RateLimitInfo {
+            limit_requests: count("x-ratelimit-limit-requests"),
+            limit_tokens: count("x-ratelimit-limit-tokens"),
+            remaining_requests: count("x-ratelimit-remaining-requests"),
+            remaining_tokens: count("x-ratelimit-remaining-tokens"),
+            reset_requests: reset("x-ratelimit-reset-requests"),
+            reset_tokens: reset("x-ratelimit-reset-tokens"),
+        }
+    }
+}
+
+/// Parses durations in the format used by the api, e.g. `1s`, `6m0s` or `20ms`
+fn parse_duration(value: &str) -> Option<Duration> {
+    let mut rest = value.trim();
+    let mut total = Duration::ZERO;
+    if rest.is_empty() {
+        return None;
+    }
+    while !rest.is_empty() {
+        let unit_start = rest.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+        let amount: f64 = rest[..unit_start].parse().ok()?;
+        rest = &rest[unit_start..];
+        let unit_end = rest
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let seconds = match &rest[..unit_end] {
+            "h" => 3600.0,
+            "m" => 60.0,
+            "s" => 1.0,
+            "ms" => 0.001,
+            _ => return None,
+        };
+        total += Duration::from_secs_f64(amount * seconds);
+        rest = &rest[unit_end..];
+    }
+    Some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use reqwest::header::{HeaderMap, HeaderValue};
+
+    use super::{parse_duration, RateLimitInfo};
+
+    #[test]
+    fn durations() {
+        assert_eq!(parse_duration("1s"), Some(Duration::from_secs(1)));
+        assert_eq!(parse_duration("6m0s"), Some(Duration::from_secs(360)));
+        assert_eq!(parse_duration("20ms"), Some(Duration::from_millis(20)));
+        assert_eq!(
+            parse_duration("1h2m3.5s"),
+            Some(Duration::from_secs_f64(3723.5))
+        );
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("soon"), None);
+    }
+    #[test]
+    fn from_headers() {
+        let mut headers = HeaderMap::new();
+        for (name, value) in [
+            ("x-ratelimit-limit-requests", "60"),
+            ("x-ratelimit-limit-tokens", "150000"),
+            ("x-ratelimit-remaining-requests", "59"),
+            ("x-ratelimit-remaining-tokens", "149984"),
+            ("x-ratelimit-reset-requests", "1s"),
+            ("x-ratelimit-reset-tokens", "6m0s"),
+        ] {
+            headers.insert(name, HeaderValue::from_static(value));
+        }
+
+        let info = RateLimitInfo::from_headers(&headers);
+
+        assert_eq!(info.limit_requests, Some(60));
+        assert_eq!(info.remaining_tokens, Some(149984));
+        assert_eq!(info.reset_requests, Some(Duration::from_secs(1)));
+        assert_eq!(info.reset_tokens, Some(Duration::from_secs(360)));
+        assert_eq!(
+            RateLimitInfo::from_headers(&HeaderMap::new()),
+            RateLimitInfo::default()
+        );
+    }
+}
@@ -0,0 +1,121 @@
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+
+use crate::ApiError;
+
+/// Controls how often a request is retried after a rate limit (429) or server error (5xx)
+///
+/// The delay before each retry doubles, starting at `base_delay`.
+/// If the response contains a `Retry-After` header, its delay is used instead.
+/// Either delay is capped at `max_delay`.
+///
+/// An exhausted quota (`insufficient_quota`) is also sent with status 429, but isn't retried.
+///
+/// Requests with a body that can't be cloned, like file uploads, are never retried.
+/// # Example
+/// ```ignore
+/// let client = Client::builder(token)
+///     .retry_policy(RetryPolicy::new(3, Duration::from_millis(500)))
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The maximum amount of retries, `0` disables retrying
+    pub max_retries: u32,
+    /// The delay before the first retry
+    pub base_delay: Duration,
+    /// The longest delay before a retry, 60 seconds by default
+    pub max_delay: Duration,
+}
+impl RetryPolicy {
+    pub fn new(max_retries: u32, base_delay: Duration) -> Self {
+        RetryPolicy {
+            max_retries,
+            base_delay,
+            max_delay: DEFAULT_MAX_DELAY,
+        }
+    }
+    /// Sets the longest delay before a retry
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+    /// A policy that never retries
+    pub fn none() -> Self {
+        RetryPolicy::new(0, Duration::ZERO)
+    }
+    pub(crate) fn should_retry(&self, error: &ApiError) -> bool {
+        error.is_rate_limit() || (500..600).contains(&error.status)
+    }
+    /// The delay before retrying for the `attempt`th time, starting at 0
+    pub(crate) fn delay(&self, attempt: u32, headers: &HeaderMap) -> Duration {
+        let retry_after = headers
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<u64>().ok());
+        let delay = match retry_after {
+            Some(seconds) => Duration::from_secs(seconds),
+            None => self.base_delay.saturating_mul(2u32.saturating_pow(attempt)),
+        };
+        delay.min(self.max_delay)
+    }
+}
+/// The default [`max_delay`](RetryPolicy::max_delay) of a policy
+const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(60);
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy::none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use reqwest::header::{HeaderMap, HeaderValue, RETRY_AFTER};
+    use reqwest::StatusCode;
+
+    use super::RetryPolicy;
+    use crate::ApiError;
+
+    #[test]
+    fn exponential_delay() {
+        let policy = RetryPolicy::new(3, Duration::from_millis(100));
+        let headers = HeaderMap::new();
+
+        assert_eq!(policy.delay(0, &headers), Duration::from_millis(100));
+        assert_eq!(policy.delay(1, &headers), Duration::from_millis(200));
+        assert_eq!(policy.delay(2, &headers), Duration::from_millis(400));
+    }
+    #[test]
+    fn retry_after() {
+        let policy = RetryPolicy::new(3, Duration::from_millis(100));
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("7"));
+
+        assert_eq!(policy.delay(2, &headers), Duration::from_secs(7));
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("86400"));
+        assert_eq!(policy.delay(0, &headers), Duration::from_secs(60));
+        let policy = policy.max_delay(Duration::from_secs(5));
+        assert_eq!(policy.delay(0, &headers), Duration::from_secs(5));
+        assert_eq!(policy.delay(10, &HeaderMap::new()), Duration::from_secs(5));
+    }
+    #[test]
+    fn retried_statuses() {
+        let policy = RetryPolicy::default();
+        let error = |status: u16, body: &str| {
+            ApiError::from_body(StatusCode::from_u16(status).unwrap(), body)
+        };
+
+        assert!(policy.should_retry(&error(429, "")));
+        assert!(policy.should_retry(&error(502, "Bad Gateway")));
+        assert!(!policy.should_retry(&error(400, "")));
+        assert!(!policy.should_retry(&error(
+            429,
+            r#"{"error": {"message": "You exceeded your current quota", "type": "insufficient_quota", "param": null, "code": "insufficient_quota"}}"#
+        )));
+    }
+}
@@ -0,0 +1,254 @@
+//! Handling of streamed responses, sent as server-sent events
+use serde::de::DeserializeOwned;
+
+use crate::error::ClientError;
+
+/// The chunks of a streamed response
+///
+/// The stream ends after the api sent `data: [DONE]`.
+/// It owns the connection, dropping it early cancels the request.
+#[cfg(not(feature = "blocking"))]
+pub type ChunkStream<T> =
+    std::pin::Pin<Box<dyn futures_util::Stream<Item = Result<T, ClientError>> + Send>>;
+/// The chunks of a streamed response
+///
+/// The iterator ends after the api sent `data: [DONE]`.
+/// It owns the connection, dropping it early cancels the request.
+#[cfg(feature = "blocking")]
+pub type ChunkStream<T> = Box<dyn Iterator<Item = Result<T, ClientError>> + Send>;
+
+enum Event<T> {
+    Chunk(Result<T, ClientError>),
+    Done,
+    Ignore,
+}
+
+/// Splits the bytes of an event stream into events.
+///
+/// The body arrives in arbitrary chunks, so an event (or a single utf-8 character) can be split
+/// across several of them. Bytes are buffered until the empty line ending an event was received.
+#[derive(Default)]
+struct Decoder {
+    buffer: Vec<u8>,
+}
+impl Decoder {
+    fn push(&mut self, bytes: &[u8]) {
+        // line endings can be `\r\n`, a raw `\r` never appears inside the json of an event
+        self.buffer
+            .extend(bytes.iter().copied().filter(|&byte| byte != b'\r'));
+    }
+    /// Returns the next complete event
+    fn next_event(&mut self) -> Option<String> {
+        let end = self.buffer.windows(2).position(|bytes| bytes == b"\n\n")?;
+        let event = self.buffer.drain(..end + 2).collect::<Vec<u8>>();
+        Some(String::from_utf8_lossy(&event[..end]).into_owned())
+    }
+    /// Returns the remaining bytes as the last event, for streams that end without an empty line
+    fn finish(&mut self) -> Option<String> {
+        let event = String::from_utf8_lossy(&std::mem::take(&mut self.buffer)).into_owned();
+        (!event.trim().is_empty()).then_some(event)
+    }
+}
+
+/// Parses a single event of the event stream.
+/// Only `data` fields are used, everything else (comments, `event`, `id`) is ignored.
+/// A `data` field spanning multiple lines is joined with newlines.
+fn parse_event<T: DeserializeOwned>(event: &str) -> Event<T> {
+    let data = event
+        .lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .map(|data| data.strip_prefix(' ').unwrap_or(data))
+        .collect::<Vec<_>>();
+    if data.is_empty() {
+        return Event::Ignore;
+    }
+    let data = data.join("\n");
+    if data.trim() == "[DONE]" {
+        return Event::Done;
+    }
+    Event::Chunk(serde_json::from_str(&data).map_err(ClientError::Deserialize))
+}
+
+#[cfg(not(feature = "blocking"))]
+pub(crate) fn chunks<T, S, B>(body: S) -> ChunkStream<T>
+where
+    T: DeserializeOwned + Send + 'static,
+    S: futures_util::Stream<Item = reqwest::Result<B>> + Send + 'static,
+    B: AsRef<[u8]>,
+{
+    use futures_util::StreamExt;
+
+    struct State<S> {
+        body: std::pin::Pin<Box<S>>,
+        decoder: Decoder,
+        done: bool,
+    }
+    let state = State {
+        body: Box::pin(body),
+        decoder: Decoder::default(),
+        done: false,
+    };
+    Box::pin(futures_util::stream::unfold(
+        state,
+        |mut state| async move {
+            loop {
+                let event = match state.done {
+                    false => state.decoder.next_event(),
+                    true => state.decoder.finish(),
+                };
+                if let Some(event) = event {
+                    match parse_event(&event) {
+                        Event::Chunk(chunk) => return Some((chunk, state)),
+                        Event::Done => return None,
+                        Event::Ignore => continue,
+                    }
+                }
+                if state.done {
+                    return None;
+                }
+                match state.body.next().await {
+                    Some(Ok(bytes)) => state.decoder.push(bytes.as_ref()),
+                    Some(Err(error)) => {
+                        state.done = true;
+                        state.decoder = Decoder::default();
+                        return Some((Err(error.into()), state));
+                    }
+                    None => state.done = true,
+                }
+            }
+        },
+    ))
+}
+
+#[cfg(feature = "blocking")]
+pub(crate) fn chunks<T>(response: crate::Response) -> ChunkStream<T>
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    chunks_from_reader(response)
+}
+
+#[cfg(feature = "blocking")]
+fn chunks_from_reader<T, R>(mut reader: R) -> ChunkStream<T>
+where
+    T: DeserializeOwned + Send + 'static,
+    R: std::io::Read + Send + 'static,
+{
+    let mut decoder = Decoder::default();
+    let mut buffer = vec![0; 8 * 1024];
+    let mut done = false;
+    Box::new(std::iter::from_fn(move || loop {
+        let event = match done {
+            false => decoder.next_event(),
+            true => decoder.finish(),
+        };
+        if let Some(event) = event {
+            match parse_event(&event) {
+                Event::Chunk(chunk) => return Some(chunk),
+                Event::Done => {
+                    done = true;
+                    decoder = Decoder::default();
+                    continue;
+                }
+                Event::Ignore => continue,
+            }
+        }
+        if done {
+            return None;
+        }
+        match reader.read(&mut buffer) {
+            Ok(0) => done = true,
+            Ok(read) => decoder.push(&buffer[..read]),
+            Err(error) if error.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(error) => {
+                done = true;
+                return Some(Err(ClientError::Io(error)));
+            }
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::completions::Chunk;
+
+    /// Streams the body a few bytes at a time and joins the texts of the chunks
+    #[cfg(feature = "blocking")]
+    fn texts(body: &'static str, size: usize) -> String {
+        /// Hands out the bytes of the body a few at a time
+        struct Split {
+            body: &'static [u8],
+            size: usize,
+        }
+        impl std::io::Read for Split {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                let read = self.size.min(buf.len()).min(self.body.len());
+                buf[..read].copy_from_slice(&self.body[..read]);
+                self.body = &self.body[read..];
+                Ok(read)
+            }
+        }
+        super::chunks_from_reader::<Chunk, _>(Split {
+            body: body.as_bytes(),
+            size,
+        })
+        .map(|chunk| chunk.unwrap().choices[0].text.clone())
+        .collect()
+    }
+    /// Streams the body a few bytes at a time and joins the texts of the chunks
+    #[cfg(not(feature = "blocking"))]
+    fn texts(body: &'static str, size: usize) -> String {
+        use futures_util::StreamExt;
+
+        let body = body
+            .as_bytes()
+            .chunks(size)
+            .map(|bytes| Ok::<_, reqwest::Error>(bytes.to_vec()))
+            .collect::<Vec<_>>();
+        crate::test_util::block_on(
+            super::chunks::<Chunk, _, _>(futures_util::stream::iter(body))
+                .map(|chunk| chunk.unwrap().choices[0].text.clone())
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn stops_on_done() {
+        let body = "data: {\"id\":\"cmpl-1\",\"object\":\"text_completion\",\"created\":1,\"model\":\"text-davinci-002\",\"choices\":[{\"text\":\"Hello\",\"index\":0,\"logprobs\":null,\"finish_reason\":null}]}\n\n\
+            data: {\"id\":\"cmpl-1\",\"object\":\"text_completion\",\"created\":1,\"model\":\"text-davinci-002\",\"choices\":[{\"text\":\" world\",\"index\":0,\"logprobs\":null,\"finish_reason\":\"stop\"}]}\n\n\
+            data: [DONE]\n\n\
+            data: {}\n\n";
+
+        assert_eq!(texts(body, body.len()), "Hello world");
+    }
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn from_response() {
+        let body = "data: {\"id\":\"cmpl-1\",\"object\":\"text_completion\",\"created\":1,\"model\":\"text-davinci-002\",\"choices\":[{\"text\":\"Hello\",\"index\":0,\"logprobs\":null,\"finish_reason\":\"stop\"}]}\n\n\
+            data: [DONE]\n\n";
+        let response: crate::Response = http::Response::new(body).into();
+
+        let text = super::chunks::<Chunk>(response)
+            .map(|chunk| chunk.unwrap().choices[0].text.clone())
+            .collect::<String>();
+
+        assert_eq!(text, "Hello");
+    }
+    #[test]
+    fn partial_events() {
+        // a multi-line data field, `\r\n` line endings, a comment and a multi-byte character
+        let body = ": keep-alive\r\n\r\n\
+            data: {\"id\":\"cmpl-1\",\"object\":\"text_completion\",\"created\":1,\"model\":\"text-davinci-002\",\r\n\
+            data: \"choices\":[{\"text\":\"Hällo\",\"index\":0,\"logprobs\":null,\"finish_reason\":null}]}\r\n\r\n\
+            data: {\"id\":\"cmpl-1\",\"object\":\"text_completion\",\"created\":1,\"model\":\"text-davinci-002\",\"choices\":[{\"text\":\" wörld\",\"index\":0,\"logprobs\":null,\"finish_reason\":\"stop\"}]}\n\n\
+            data: [DONE]\n\n";
+
+        for size in [1, 2, 3, 7, 64, body.len()] {
+            assert_eq!(
+                texts(body, size),
+                "Hällo wörld",
+                "split into chunks of {size} bytes"
+            );
+        }
+    }
+}
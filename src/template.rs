@@ -0,0 +1,127 @@
+//! Prompt templates with named variables, e.g. `Translate {text} to {language}`
+use std::{collections::HashMap, fmt};
+
+/// A prompt with `{name}` placeholders that are replaced by variables when rendering
+///
+/// Literal braces are written as `{{` and `}}`.
+/// # Example
+/// ```
+/// # use std::collections::HashMap;
+/// # use gpt3_rs::Template;
+/// let template = Template::new("Translate {text} to {language}");
+/// let prompt = template
+///     .render(&HashMap::from([("text", "Hello"), ("language", "French")]))
+///     .unwrap();
+///
+/// assert_eq!(prompt, "Translate Hello to French");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    text: String,
+}
+impl Template {
+    pub fn new(text: impl Into<String>) -> Self {
+        Template { text: text.into() }
+    }
+    /// The template text, with its placeholders
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+    /// Replaces every placeholder with the variable of the same name
+    ///
+    /// Fails if a placeholder has no variable or a brace isn't closed, unused variables are ignored.
+    pub fn render(&self, vars: &HashMap<&str, &str>) -> Result<String, TemplateError> {
+        let mut rendered = String::with_capacity(self.text.len());
+        let mut rest = self.text.as_str();
+        while let Some(brace) = rest.find(['{', '}']) {
+            rendered.push_str(&rest[..brace]);
+            let position = self.text.len() - rest.len() + brace;
+            let after = &rest[brace + 1..];
+            match (&rest[brace..=brace], after.chars().next()) {
+                ("{", Some('{')) => {
+                    rendered.push('{');
+                    rest = &after[1..];
+                }
+                ("}", Some('}')) => {
+                    rendered.push('}');
+                    rest = &after[1..];
+                }
+                ("{", _) => {
+                    let end = after.find('}').ok_or(TemplateError::Unclosed(position))?;
+                    let name = after[..end].trim();
+                    let value = vars
+                        .get(name)
+                        .ok_or_else(|| TemplateError::MissingVariable(name.to_string()))?;
+                    rendered.push_str(value);
+                    rest = &after[end + 1..];
+                }
+                _ => return Err(TemplateError::Unopened(position)),
+            }
+        }
+        rendered.push_str(rest);
+        Ok(rendered)
+    }
+}
+impl From<&str> for Template {
+    fn from(text: &str) -> Self {
+        Template::new(text)
+    }
+}
+
+/// An error returned when rendering a [`Template`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    /// No variable was given for the placeholder with this name
+    MissingVariable(String),
+    /// A `{` without a closing `}`, at this byte position of the template
+    Unclosed(usize),
+    /// A `}` without an opening `{`, at this byte position of the template
+    Unopened(usize),
+}
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemplateError::MissingVariable(name) => write!(f, "missing variable `{name}`"),
+            TemplateError::Unclosed(_) => write!(f, "unclosed `{{` in template"),
+            TemplateError::Unopened(_) => write!(
+                f,
+                "unopened `}}` in template, use `}}}}` for a literal brace"
+            ),
+        }
+    }
+}
+impl std::error::Error for TemplateError {}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{Template, TemplateError};
+
+    #[test]
+    fn render() {
+        let vars = HashMap::from([("text", "Hello"), ("language", "French")]);
+
+        assert_eq!(
+            Template::new("Translate { text } to {language}").render(&vars),
+            Ok("Translate Hello to French".to_string())
+        );
+        assert_eq!(
+            Template::new("{{\"text\": \"{text}\"}}").render(&vars),
+            Ok("{\"text\": \"Hello\"}".to_string())
+        );
+        assert_eq!(
+            Template::new("{text} is a greeting").render(&HashMap::new()),
+            Err(TemplateError::MissingVariable("text".to_string()))
+        );
+        assert_eq!(
+            Template::new("{text} {language").render(&vars),
+            Err(TemplateError::Unclosed(7))
+        );
+        assert_eq!(
+            Template::new("Translate text}").render(&vars),
+            Err(TemplateError::Unopened(14))
+        );
+    }
+}
@@ -0,0 +1,25 @@
+//! Helpers to run the same tests with the async and the blocking client, see the `wait!` macro
+use crate::{stream::ChunkStream, ClientError};
+
+/// Runs a future on a runtime of the current test thread
+///
+/// The runtime is kept for the whole test, since reqwest ties its connections to the runtime they were opened on.
+#[cfg(not(feature = "blocking"))]
+pub(crate) fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    thread_local! {
+        static RUNTIME: tokio::runtime::Runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+    }
+    RUNTIME.with(|runtime| runtime.block_on(future))
+}
+
+/// Reads all chunks of a streamed response
+pub(crate) fn collect_chunks<T>(stream: ChunkStream<T>) -> Vec<Result<T, ClientError>> {
+    #[cfg(not(feature = "blocking"))]
+    let chunks = block_on(futures_util::StreamExt::collect(stream));
+    #[cfg(feature = "blocking")]
+    let chunks = stream.collect();
+    chunks
+}
@@ -0,0 +1,83 @@
+//! Count, encode and decode tokens the same way the api does
+//!
+//! Requires the `tokenizer` feature.
+//! # Example
+//! ```ignore
+//! let prompt = "Say this is a test";
+//! let max_tokens = 2048 - tokenizer::count_tokens(&Model::Curie, prompt);
+//! ```
+use std::collections::HashMap;
+
+use tiktoken_rs::{tokenizer::Tokenizer, CoreBPE};
+
+use crate::model::Model;
+
+/// A token id as used by the api, e.g. in `logit_bias`
+pub type Token = u32;
+
+/// The byte pair encoding used by a model
+fn bpe(model: &Model) -> &'static CoreBPE {
+    let tokenizer = match model {
+        Model::Ada | Model::Babbage | Model::Curie => Tokenizer::R50kBase,
+        Model::Davinci | Model::Davinci003 | Model::CodeDavinci002 => Tokenizer::P50kBase,
+        Model::TextEmbeddingAda002 => Tokenizer::Cl100kBase,
+        // fine-tuned models use the encoding of their base model
+        Model::Custom(id) => {
+            tiktoken_rs::tokenizer::get_tokenizer(id).unwrap_or(Tokenizer::R50kBase)
+        }
+    };
+    tiktoken_rs::bpe_for_tokenizer(tokenizer).expect("the encodings are bundled with the crate")
+}
+
+/// Counts the tokens the model will use for `text`
+pub fn count_tokens(model: &Model, text: &str) -> usize {
+    encode(model, text).len()
+}
+/// Encodes `text` into the tokens used by the model
+///
+/// Special tokens like `<|endoftext|>` are encoded as their token instead of as text.
+pub fn encode(model: &Model, text: &str) -> Vec<Token> {
+    bpe(model).encode_with_special_tokens(text)
+}
+/// Decodes tokens of the model back into text
+///
+/// Returns `None` if a token is unknown to the model or the tokens don't form valid utf-8.
+pub fn decode(model: &Model, tokens: &[Token]) -> Option<String> {
+    bpe(model).decode(tokens).ok()
+}
+
+/// Adds `value` as the bias of every token of `text` to the `logit_bias` field of a builder.
+/// Builders without a model use the GPT-3 encoding.
+pub(crate) fn bias_tokens(
+    logit_bias: &mut Option<Option<HashMap<String, i16>>>,
+    model: Option<&Model>,
+    text: &str,
+    value: i16,
+) {
+    let map = logit_bias
+        .get_or_insert_with(|| Some(HashMap::new()))
+        .get_or_insert_with(HashMap::new);
+    for token in encode(model.unwrap_or(&Model::Ada), text) {
+        map.insert(token.to_string(), value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{count_tokens, decode, encode};
+    use crate::Model;
+
+    #[test]
+    fn gpt3_encoding() {
+        assert_eq!(encode(&Model::Curie, "hello world"), [31373, 995]);
+        assert_eq!(encode(&Model::Curie, "<|endoftext|>"), [50256]);
+        assert_eq!(count_tokens(&Model::Davinci, "Say this is a test"), 5);
+    }
+    #[test]
+    fn round_trip() {
+        let text = "Don't repeat yourself 🦀";
+        for model in [Model::Ada, Model::Davinci003, Model::TextEmbeddingAda002] {
+            assert_eq!(decode(&model, &encode(&model, text)).unwrap(), text);
+        }
+    }
+}
@@ -0,0 +1,197 @@
+//! The http layer of a [`Client`](crate::Client), replaceable e.g. to test code using the client offline
+//!
+//! # Example
+//! ```ignore
+//! let transport = Arc::new(MockTransport::new().respond(200, r#"{"object": "list", "data": []}"#));
+//! let client = Client::with_transport(token, transport.clone());
+//!
+//! let files = files::list::Request::default().request(&client).await?;
+//!
+//! assert_eq!(transport.requests()[0].url.path(), "/v1/files");
+//! ```
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use reqwest::{header::HeaderMap, Method, Url};
+
+use crate::{ClientError, HttpRequest};
+
+/// Sends a built request and returns the response
+///
+/// Implemented by the reqwest client, which is used by default,
+/// and by [`MockTransport`] to respond with canned responses.
+#[cfg_attr(not(feature = "blocking"), async_trait::async_trait)]
+pub trait Transport: Send + Sync {
+    /// Sends the request, retries and errors are handled by the [`Client`](crate::Client)
+    #[cfg(not(feature = "blocking"))]
+    async fn send(&self, request: HttpRequest) -> Result<crate::Response, ClientError>;
+    /// Sends the request, retries and errors are handled by the [`Client`](crate::Client)
+    #[cfg(feature = "blocking")]
+    fn send(&self, request: HttpRequest) -> Result<crate::Response, ClientError>;
+}
+
+#[cfg(not(feature = "blocking"))]
+#[async_trait::async_trait]
+impl Transport for reqwest::Client {
+    async fn send(&self, request: HttpRequest) -> Result<crate::Response, ClientError> {
+        Ok(self.execute(request).await?)
+    }
+}
+#[cfg(feature = "blocking")]
+impl Transport for reqwest::blocking::Client {
+    fn send(&self, request: HttpRequest) -> Result<crate::Response, ClientError> {
+        Ok(self.execute(request)?)
+    }
+}
+
+#[cfg_attr(not(feature = "blocking"), async_trait::async_trait)]
+impl<T: Transport + ?Sized> Transport for Arc<T> {
+    #[cfg(not(feature = "blocking"))]
+    async fn send(&self, request: HttpRequest) -> Result<crate::Response, ClientError> {
+        (**self).send(request).await
+    }
+    #[cfg(feature = "blocking")]
+    fn send(&self, request: HttpRequest) -> Result<crate::Response, ClientError> {
+        (**self).send(request)
+    }
+}
+
+/// A request received by a [`MockTransport`]
+#[derive(Debug, Clone)]
+pub struct MockRequest {
+    /// The http method of the request
+    pub method: Method,
+    /// The url of the request
+    pub url: Url,
+    /// The headers of the request
+    pub headers: HeaderMap,
+    /// The body of the request, `None` for requests without a body or with a streamed body like file uploads
+    pub body: Option<Vec<u8>>,
+}
+impl MockRequest {
+    /// Parses the body as json
+    pub fn json(&self) -> Option<serde_json::Value> {
+        serde_json::from_slice(self.body.as_deref()?).ok()
+    }
+}
+
+/// A transport that doesn't send anything and responds with canned responses instead
+///
+/// The responses are returned in the order they were added, once they are used up
+/// requests fail with a [`ClientError::Io`].
+/// All received requests are recorded, wrap the transport in an [`Arc`] to inspect them.
+#[derive(Debug, Default)]
+pub struct MockTransport {
+    responses: Mutex<VecDeque<(u16, Vec<u8>)>>,
+    requests: Mutex<Vec<MockRequest>>,
+}
+impl MockTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Adds a response with the status and json body
+    ///
+    /// The body can also be binary, e.g. `vec![0xff, 0xfb]` for an mp3 file.
+    pub fn respond(self, status: u16, body: impl Into<Vec<u8>>) -> Self {
+        self.responses
+            .lock()
+            .unwrap()
+            .push_back((status, body.into()));
+        self
+    }
+    /// The requests received so far
+    pub fn requests(&self) -> Vec<MockRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+    fn respond_to(&self, request: HttpRequest) -> Result<crate::Response, ClientError> {
+        self.requests.lock().unwrap().push(MockRequest {
+            method: request.method().clone(),
+            url: request.url().clone(),
+            headers: request.headers().clone(),
+            body: request
+                .body()
+                .and_then(|body| body.as_bytes())
+                .map(<[u8]>::to_vec),
+        });
+        let (status, body) = self.responses.lock().unwrap().pop_front().ok_or_else(|| {
+            ClientError::Io(std::io::Error::other(
+                "the mock transport has no responses left",
+            ))
+        })?;
+        let response = http::Response::builder()
+            .status(status)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body)
+            .map_err(|error| {
+                ClientError::Io(std::io::Error::new(std::io::ErrorKind::InvalidInput, error))
+            })?;
+        Ok(response.into())
+    }
+}
+#[cfg_attr(not(feature = "blocking"), async_trait::async_trait)]
+impl Transport for MockTransport {
+    #[cfg(not(feature = "blocking"))]
+    async fn send(&self, request: HttpRequest) -> Result<crate::Response, ClientError> {
+        self.respond_to(request)
+    }
+    #[cfg(feature = "blocking")]
+    fn send(&self, request: HttpRequest) -> Result<crate::Response, ClientError> {
+        self.respond_to(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::MockTransport;
+    use crate::api::completions;
+    use crate::{Client, ClientError, Model, Request};
+
+    #[test]
+    fn canned_responses() {
+        let transport = Arc::new(
+            MockTransport::new()
+                .respond(
+                    200,
+                    r#"{"id": "cmpl-1", "object": "text_completion", "created": 1, "model": "ada", "choices": [{"text": " 3", "index": 0, "logprobs": null, "finish_reason": "stop"}]}"#,
+                )
+                .respond(
+                    400,
+                    r#"{"error": {"message": "bad request", "type": "invalid_request_error", "param": null, "code": null}}"#,
+                ),
+        );
+        let client = Client::with_transport("token".to_string(), transport.clone());
+        let request = completions::Builder::default()
+            .model(Model::Ada)
+            .prompt("what is 1 + 2?")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            wait!(request.request(&client)).unwrap().choices[0].text,
+            " 3"
+        );
+        assert!(
+            matches!(wait!(request.request(&client)), Err(ClientError::Api(error)) if error.status == 400)
+        );
+        assert!(matches!(
+            wait!(request.request(&client)),
+            Err(ClientError::Io(_))
+        ));
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(
+            requests[0].url.as_str(),
+            "https://api.openai.com/v1/engines/text-ada-001/completions"
+        );
+        assert_eq!(requests[0].headers["Authorization"], "Bearer token");
+        assert_eq!(
+            requests[0].json().unwrap()["prompt"],
+            serde_json::json!(["what is 1 + 2?"])
+        );
+    }
+}
@@ -0,0 +1,93 @@
+//! Every form `IntoVec` can be created from is accepted by the builders
+use gpt3_rs::{
+    api::{classifications, embeddings},
+    IntoVec, Model,
+};
+
+fn classifications(
+    examples: impl Into<IntoVec<Vec<String>>>,
+    labels: impl Into<IntoVec<String>>,
+) -> classifications::Request {
+    classifications::Builder::default()
+        .model(Model::Curie)
+        .query("It is a rainy day :(")
+        .examples(examples)
+        .labels(labels)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn examples_and_labels() {
+    let expected = classifications(
+        vec![
+            vec!["A happy moment".to_string(), "Positive".to_string()],
+            vec!["I am sad.".to_string(), "Negative".to_string()],
+        ],
+        vec!["Positive".to_string(), "Negative".to_string()],
+    );
+    let example = |text: &str, label: &str| vec![text.to_string(), label.to_string()];
+
+    for request in [
+        classifications(
+            &[&["A happy moment", "Positive"], &["I am sad.", "Negative"]],
+            ["Positive", "Negative"],
+        ),
+        classifications(
+            vec![
+                vec!["A happy moment", "Positive"],
+                vec!["I am sad.", "Negative"],
+            ],
+            vec!["Positive", "Negative"],
+        ),
+        classifications(
+            [
+                example("A happy moment", "Positive"),
+                example("I am sad.", "Negative"),
+            ],
+            ["Positive", "Negative"],
+        ),
+        classifications(
+            &[
+                example("A happy moment", "Positive"),
+                example("I am sad.", "Negative"),
+            ][..],
+            &["Positive".to_string(), "Negative".to_string()][..],
+        ),
+        classifications(
+            [("A happy moment", "Positive"), ("I am sad.", "Negative")]
+                .into_iter()
+                .map(|(text, label)| example(text, label))
+                .collect::<IntoVec<_>>(),
+            ["Positive", "Negative"]
+                .into_iter()
+                .map(String::from)
+                .collect::<IntoVec<_>>(),
+        ),
+    ] {
+        assert_eq!(request, expected);
+    }
+}
+
+#[test]
+fn input() {
+    let request = |input: IntoVec<String>| {
+        embeddings::Builder::default()
+            .model(Model::TextEmbeddingAda002)
+            .input(input)
+            .build()
+            .unwrap()
+    };
+    let expected = request(vec!["first".to_string(), "second".to_string()].into());
+
+    for input in [
+        IntoVec::from(&["first", "second"]),
+        IntoVec::from(["first", "second"]),
+        IntoVec::from(vec!["first", "second"]),
+        IntoVec::from(["first".to_string(), "second".to_string()]),
+        ["first", "second"].iter().map(|s| s.to_string()).collect(),
+    ] {
+        assert_eq!(request(input), expected);
+    }
+    assert_eq!(&*request("only".into()).input, ["only"]);
+}